@@ -0,0 +1,249 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::save::{self, SaveHeader};
+
+pub const SAVE_EXTENSION: &str = "sav";
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// What the save browser shows about a save.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSummary {
+    pub path: PathBuf,
+    pub header: Option<SaveHeader>,
+    pub soldier_count: usize,
+    pub mods: Vec<String>,
+}
+
+impl SaveSummary {
+    /// The name the game gave the save, or the file name for saves without a header.
+    pub fn name(&self) -> String {
+        match &self.header {
+            Some(header) => header.name.clone(),
+            None => self
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_iron_man(&self) -> bool {
+        self.header
+            .as_ref()
+            .is_some_and(|header| header.is_iron_man())
+    }
+}
+
+/// What the save browser can be ordered by.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BrowserSortKey {
+    Name,
+    GameTime,
+    /// When the save was made in the real world
+    #[default]
+    SavedAt,
+    Soldiers,
+}
+
+impl BrowserSortKey {
+    pub const ALL: [BrowserSortKey; 4] = [
+        BrowserSortKey::Name,
+        BrowserSortKey::GameTime,
+        BrowserSortKey::SavedAt,
+        BrowserSortKey::Soldiers,
+    ];
+
+    fn compare(&self, a: &SaveSummary, b: &SaveSummary) -> Ordering {
+        let time = |summary: &SaveSummary, game_time: bool| {
+            summary.header.as_ref().and_then(|header| {
+                time_key(if game_time {
+                    &header.game_time
+                } else {
+                    &header.saved_at
+                })
+            })
+        };
+        match self {
+            BrowserSortKey::Name => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+            BrowserSortKey::GameTime => time(a, true).cmp(&time(b, true)),
+            BrowserSortKey::SavedAt => time(a, false).cmp(&time(b, false)),
+            BrowserSortKey::Soldiers => a.soldier_count.cmp(&b.soldier_count),
+        }
+    }
+}
+
+impl std::fmt::Display for BrowserSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BrowserSortKey::Name => "Name",
+                BrowserSortKey::GameTime => "In-game date",
+                BrowserSortKey::SavedAt => "Saved",
+                BrowserSortKey::Soldiers => "Soldiers",
+            }
+        )
+    }
+}
+
+/// Every save directly inside the folders, in no particular order.
+pub fn list_saves(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut saves = vec![];
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_some_and(|ext| ext == SAVE_EXTENSION) && !saves.contains(&path) {
+                saves.push(path);
+            }
+        }
+    }
+    saves
+}
+
+pub fn summarise_save(path: &Path) -> Result<SaveSummary, Box<dyn Error>> {
+    let file = fs::read(path)?;
+    let (_, save) = save::parse_save(&file).map_err(|err| err.to_owned())?;
+    Result::Ok(SaveSummary {
+        path: path.to_path_buf(),
+        header: save.header(),
+        soldier_count: save.soldiers.len(),
+        mods: save.used_mods().unwrap_or_default(),
+    })
+}
+
+/// The saves matching the search, which can be part of a save's name, file name or mods, in
+/// the order asked for.
+pub fn browse<'a>(
+    saves: &'a [SaveSummary],
+    search: &str,
+    sort_key: BrowserSortKey,
+    descending: bool,
+) -> Vec<&'a SaveSummary> {
+    let search = search.trim().to_lowercase();
+    let mut matches: Vec<_> = saves
+        .iter()
+        .filter(|summary| {
+            search.is_empty()
+                || summary.name().to_lowercase().contains(&search)
+                || summary
+                    .path
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .contains(&search)
+                || summary
+                    .mods
+                    .iter()
+                    .any(|name| name.to_lowercase().contains(&search))
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        let ordering = sort_key.compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    matches
+}
+
+/// Turns a time written like `23:50 06 Jul 2024` into something that sorts in time order.
+fn time_key(time: &str) -> Option<(u32, usize, u32, u32, u32)> {
+    let mut parts = time.split_whitespace();
+    let (hour, minute) = parts.next()?.split_once(':')?;
+    let day = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|name| *name == month)?;
+    let year = parts.next()?.parse().ok()?;
+    Some((year, month, day, hour.parse().ok()?, minute.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::fixtures_dir;
+
+    #[test]
+    fn it_summarises_saves() {
+        let tests_dir = fixtures_dir();
+        let mut paths = list_saves(&[tests_dir.clone(), tests_dir.clone()]);
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "empty_save.sav",
+                "full_save.sav",
+                "random_data.sav",
+                "single_soldier.sav"
+            ]
+            .map(|name| tests_dir.join(name))
+        );
+
+        let full = summarise_save(&tests_dir.join("full_save.sav")).unwrap();
+        assert_eq!(full.name(), "Iron Man (2024-07-06_20.46.00)");
+        assert!(full.is_iron_man());
+        assert_eq!(full.soldier_count, 22);
+        assert_eq!(full.mods.len(), 18);
+
+        let single = summarise_save(&tests_dir.join("single_soldier.sav")).unwrap();
+        assert_eq!(single.name(), "single_soldier.sav");
+        assert!(!single.is_iron_man());
+        assert_eq!(single.soldier_count, 1);
+        assert!(single.mods.is_empty());
+    }
+
+    #[test]
+    fn it_searches_and_sorts_saves() {
+        let summary = |name: &str, saved_at: &str, soldier_count, mods: &[&str]| SaveSummary {
+            path: PathBuf::from(format!("/saves/{}.sav", name)),
+            header: Some(SaveHeader {
+                version: 0x0102,
+                name: name.to_string(),
+                game_time: String::from("00:00 01 Sep 1979"),
+                saved_at: saved_at.to_string(),
+            }),
+            soldier_count,
+            mods: mods.iter().map(|name| name.to_string()).collect(),
+        };
+        let saves = [
+            summary("Autosave", "09:15 12 Jan 2025", 12, &["xce"]),
+            summary("Before terror mission", "23:50 06 Jul 2024", 20, &[]),
+            summary(
+                "Iron Man (2024-08-01_10.00.00)",
+                "10:00 01 Aug 2024",
+                8,
+                &["xce"],
+            ),
+        ];
+        let counts = |matches: Vec<&SaveSummary>| {
+            matches
+                .into_iter()
+                .map(|summary| summary.soldier_count)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            counts(browse(&saves, "", BrowserSortKey::SavedAt, true)),
+            vec![12, 8, 20]
+        );
+        assert_eq!(
+            counts(browse(&saves, "XCE", BrowserSortKey::Soldiers, false)),
+            vec![8, 12]
+        );
+        assert_eq!(
+            counts(browse(&saves, "terror", BrowserSortKey::Name, false)),
+            vec![20]
+        );
+        assert_eq!(time_key("00:00 01 Sep 1979"), Some((1979, 8, 1, 0, 0)));
+        assert_eq!(time_key("not a time"), None);
+    }
+}
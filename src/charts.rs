@@ -0,0 +1,195 @@
+use std::f32::consts::PI;
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::mouse;
+use iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
+use iced::{Color, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::soldier::{SoldierStats, Stat};
+
+/// The value at the edge of a chart, which is as high as stats normally go
+const CHART_MAX: u32 = 100;
+
+const ORIGINAL_COLOR: Color = Color::from_rgb(0.2, 0.4, 0.8);
+const CURRENT_COLOR: Color = Color::from_rgb(0.9, 0.5, 0.0);
+const GRID_COLOR: Color = Color::from_rgb(0.75, 0.75, 0.75);
+
+/// A soldier's six stats on a radar chart, with the original values overlaid on the current
+/// ones.
+pub struct RadarChart {
+    pub stats: SoldierStats,
+}
+
+impl<Message> Program<Message> for RadarChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        // Leave room around the edge for the labels
+        let radius = frame.width().min(frame.height()) / 2.0 - 30.0;
+        let axes = Stat::ALL.len();
+
+        let polygon = |fractions: &[f32]| {
+            Path::new(|builder| {
+                for (axis, fraction) in fractions.iter().enumerate() {
+                    let point = radar_point(center, radius, axis, axes, *fraction);
+                    if axis == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+                builder.close();
+            })
+        };
+
+        for ring in 1..=4 {
+            let fraction = ring as f32 / 4.0;
+            frame.stroke(
+                &polygon(&vec![fraction; axes]),
+                Stroke::default().with_color(GRID_COLOR),
+            );
+        }
+        for (axis, stat) in Stat::ALL.into_iter().enumerate() {
+            frame.stroke(
+                &Path::line(center, radar_point(center, radius, axis, axes, 1.0)),
+                Stroke::default().with_color(GRID_COLOR),
+            );
+            frame.fill_text(Text {
+                content: stat.to_string(),
+                position: radar_point(center, radius + 15.0, axis, axes, 1.0),
+                size: 14.0.into(),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                ..Text::default()
+            });
+        }
+
+        let fraction = |value: u32| value.min(CHART_MAX) as f32 / CHART_MAX as f32;
+        let original: Vec<_> = self
+            .stats
+            .iter()
+            .map(|(_, _, base)| fraction(base))
+            .collect();
+        let current: Vec<_> = self
+            .stats
+            .iter()
+            .map(|(_, current, _)| fraction(current))
+            .collect();
+        for (fractions, color) in [(original, ORIGINAL_COLOR), (current, CURRENT_COLOR)] {
+            let path = polygon(&fractions);
+            frame.fill(&path, Color { a: 0.25, ..color });
+            frame.stroke(&path, Stroke::default().with_color(color).with_width(2.0));
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// How many soldiers have a stat in each bin.
+pub struct Histogram {
+    pub bins: Vec<u32>,
+}
+
+impl<Message> Program<Message> for Histogram {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let tallest = self.bins.iter().copied().max().unwrap_or(0).max(1);
+        let bar_width = frame.width() / self.bins.len().max(1) as f32;
+        // Leave room along the bottom for the bin labels
+        let chart_height = frame.height() - 16.0;
+
+        for (i, count) in self.bins.iter().enumerate() {
+            let height = chart_height * *count as f32 / tallest as f32;
+            let x = i as f32 * bar_width;
+            frame.fill_rectangle(
+                Point::new(x + 1.0, chart_height - height),
+                Size::new(bar_width - 2.0, height),
+                CURRENT_COLOR,
+            );
+            if *count > 0 {
+                frame.fill_text(Text {
+                    content: count.to_string(),
+                    position: Point::new(x + bar_width / 2.0, chart_height - height),
+                    size: 12.0.into(),
+                    horizontal_alignment: Horizontal::Center,
+                    vertical_alignment: Vertical::Bottom,
+                    ..Text::default()
+                });
+            }
+            frame.fill_text(Text {
+                content: (i as u32 * CHART_MAX / self.bins.len() as u32).to_string(),
+                position: Point::new(x, frame.height()),
+                size: 12.0.into(),
+                vertical_alignment: Vertical::Bottom,
+                ..Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Counts values into equal bins from 0 up to the top of the chart. Values over the top go in
+/// the last bin.
+pub fn histogram(values: impl IntoIterator<Item = u32>, bin_count: usize) -> Vec<u32> {
+    let mut bins = vec![0; bin_count];
+    if bin_count == 0 {
+        return bins;
+    }
+    let bin_width = CHART_MAX.div_ceil(bin_count as u32);
+    for value in values {
+        bins[((value / bin_width) as usize).min(bin_count - 1)] += 1;
+    }
+    bins
+}
+
+/// The point a fraction of the way along one of a radar chart's axes, starting from straight up
+/// and going clockwise.
+fn radar_point(center: Point, radius: f32, axis: usize, axes: usize, fraction: f32) -> Point {
+    let angle = -PI / 2.0 + 2.0 * PI * axis as f32 / axes as f32;
+    Point::new(
+        center.x + radius * fraction * angle.cos(),
+        center.y + radius * fraction * angle.sin(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_bins_values() {
+        assert_eq!(
+            histogram([0, 9, 10, 35, 99, 100, 250], 10),
+            vec![2, 1, 0, 1, 0, 0, 0, 0, 0, 3]
+        );
+        assert!(histogram([50], 0).is_empty());
+    }
+
+    #[test]
+    fn it_places_radar_points() {
+        let center = Point::new(100.0, 100.0);
+        let top = radar_point(center, 50.0, 0, 6, 1.0);
+        assert!((top.x - 100.0).abs() < 0.001 && (top.y - 50.0).abs() < 0.001);
+        let bottom = radar_point(center, 50.0, 3, 6, 0.5);
+        assert!((bottom.x - 100.0).abs() < 0.001 && (bottom.y - 125.0).abs() < 0.001);
+    }
+}
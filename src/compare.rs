@@ -0,0 +1,143 @@
+use crate::save::Save;
+use crate::soldier::{Soldier, SoldierStats, Stat};
+
+/// One stat lined up across the soldiers being compared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatComparison {
+    pub stat: Stat,
+    /// Current and original values, in the same order as the soldiers
+    pub values: Vec<(u32, u32)>,
+    /// The highest current value
+    pub best: u32,
+}
+
+impl StatComparison {
+    /// How far each soldier's current value is from the first soldier's.
+    pub fn deltas(&self) -> Vec<i64> {
+        let reference = self
+            .values
+            .first()
+            .map(|(current, _)| *current)
+            .unwrap_or(0);
+        self.values
+            .iter()
+            .map(|(current, _)| *current as i64 - reference as i64)
+            .collect()
+    }
+}
+
+pub fn compare(soldiers: &[&Soldier]) -> Vec<StatComparison> {
+    if soldiers.is_empty() {
+        return vec![];
+    }
+    Stat::ALL
+        .into_iter()
+        .map(|stat| {
+            let values: Vec<_> = soldiers
+                .iter()
+                .map(|soldier| (soldier.stats.current(stat), soldier.stats.original(stat)))
+                .collect();
+            StatComparison {
+                stat,
+                best: values
+                    .iter()
+                    .map(|(current, _)| *current)
+                    .max()
+                    .unwrap_or(0),
+                values,
+            }
+        })
+        .collect()
+}
+
+/// Copies one stat, both current and original, or all of them when no stat is given.
+pub fn copy_stats(from: &SoldierStats, to: &mut SoldierStats, stat: Option<Stat>) {
+    match stat {
+        None => *to = from.clone(),
+        Some(stat) => {
+            to.set_current(stat, from.current(stat));
+            to.set_original(stat, from.original(stat));
+        }
+    }
+}
+
+/// Copies from one soldier to each of the others listed.
+pub fn copy_stats_to(save: &mut Save, from_id: u32, to_ids: &[u32], stat: Option<Stat>) {
+    let Some(from) = save
+        .get_soldier(from_id)
+        .map(|soldier| soldier.stats.clone())
+    else {
+        return;
+    };
+    for soldier in save
+        .soldiers
+        .iter_mut()
+        .filter(|soldier| soldier.id != from_id && to_ids.contains(&soldier.id))
+    {
+        copy_stats(&from, &mut soldier.stats, stat);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::load_save;
+
+    #[test]
+    fn it_compares_soldiers() {
+        let save = load_save("full_save.sav");
+        let soldiers = [&save.soldiers[0], &save.soldiers[1], &save.soldiers[2]];
+        let comparisons = compare(&soldiers);
+        assert_eq!(comparisons.len(), 6);
+
+        let bravery = &comparisons[5];
+        assert_eq!(bravery.stat, Stat::Bravery);
+        assert_eq!(
+            bravery.values[1].0,
+            save.soldiers[1].stats.current(Stat::Bravery)
+        );
+        assert_eq!(
+            bravery.best,
+            soldiers
+                .iter()
+                .map(|soldier| soldier.stats.current(Stat::Bravery))
+                .max()
+                .unwrap()
+        );
+        assert_eq!(bravery.deltas()[0], 0);
+        assert_eq!(
+            bravery.deltas()[2],
+            save.soldiers[2].stats.current(Stat::Bravery) as i64
+                - save.soldiers[0].stats.current(Stat::Bravery) as i64
+        );
+        assert!(compare(&[]).is_empty());
+    }
+
+    #[test]
+    fn it_copies_stats_to_others() {
+        let mut save = load_save("full_save.sav");
+        let ids: Vec<_> = save.soldiers[..3]
+            .iter()
+            .map(|soldier| soldier.id)
+            .collect();
+        let before = save.soldiers[1].stats.clone();
+
+        copy_stats_to(&mut save, ids[0], &ids, Some(Stat::Health));
+        let source = save.soldiers[0].stats.clone();
+        assert_eq!(
+            save.soldiers[1].stats.current(Stat::Health),
+            source.current(Stat::Health)
+        );
+        assert_eq!(
+            save.soldiers[2].stats.original(Stat::Health),
+            source.original(Stat::Health)
+        );
+        assert_eq!(
+            save.soldiers[1].stats.current(Stat::Bravery),
+            before.current(Stat::Bravery)
+        );
+
+        copy_stats_to(&mut save, ids[0], &ids, None);
+        assert_eq!(save.soldiers[2].stats, source);
+    }
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::roster::SortKey;
+
+// Relative to the platform's config directory
+const CONFIG_FILE: &str = "xenonauts-soldier-editor/config.toml";
+
+/// How many saves are remembered on the start screen
+const RECENT_SAVE_COUNT: usize = 10;
+
+/// Editor settings that are kept between runs. Anything missing from the file, such as a
+/// setting added since it was written, takes its default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Most recently opened first
+    pub recent_saves: Vec<PathBuf>,
+    /// Where file dialogs start
+    pub last_directory: Option<PathBuf>,
+    /// Where saves are opened from, instead of the save folders found automatically
+    pub save_dir: Option<PathBuf>,
+    /// Logical width and height
+    pub window_size: Option<(f32, f32)>,
+    pub game_dir: Option<PathBuf>,
+    pub mod_dirs: Vec<PathBuf>,
+    pub sort_key: SortKey,
+    pub sort_descending: bool,
+}
+
+impl Config {
+    /// Moves the save to the top of the recent saves, and starts file dialogs next to it.
+    pub fn add_recent_save(&mut self, path: PathBuf) {
+        self.last_directory = path.parent().map(Path::to_path_buf);
+        self.recent_saves.retain(|recent| *recent != path);
+        self.recent_saves.insert(0, path);
+        self.recent_saves.truncate(RECENT_SAVE_COUNT);
+    }
+}
+
+/// Where the config is kept, if the platform has somewhere for it.
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// Reads the config, or the defaults when it hasn't been written yet.
+pub fn load_config(path: &Path) -> Result<Config, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(toml::from_str(&text)?),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save_config(path: &Path, config: &Config) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, toml::to_string(config)?)?;
+    Result::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::soldier::Stat;
+
+    #[test]
+    fn it_keeps_recent_saves_in_order() {
+        let mut config = Config::default();
+        for i in 0..12 {
+            config.add_recent_save(PathBuf::from(format!("/saves/{}.sav", i)));
+        }
+        config.add_recent_save(PathBuf::from("/saves/5.sav"));
+
+        assert_eq!(config.recent_saves.len(), RECENT_SAVE_COUNT);
+        assert_eq!(config.recent_saves[0], PathBuf::from("/saves/5.sav"));
+        assert_eq!(config.recent_saves[1], PathBuf::from("/saves/11.sav"));
+        assert_eq!(
+            config
+                .recent_saves
+                .iter()
+                .filter(|path| path.ends_with("5.sav"))
+                .count(),
+            1
+        );
+        assert_eq!(config.last_directory, Some(PathBuf::from("/saves")));
+    }
+
+    #[test]
+    fn it_round_trips_config() {
+        let path = env::temp_dir()
+            .join(format!("xenonauts-soldier-editor-{}", std::process::id()))
+            .join("config.toml");
+        assert_eq!(load_config(&path).unwrap(), Config::default());
+
+        let config = Config {
+            recent_saves: vec![PathBuf::from("/saves/a.sav")],
+            last_directory: Some(PathBuf::from("/saves")),
+            save_dir: Some(PathBuf::from("/games/saves")),
+            window_size: Some((1280.0, 720.0)),
+            game_dir: Some(PathBuf::from("/games/xenonauts")),
+            mod_dirs: vec![PathBuf::from("/mods/one"), PathBuf::from("/mods/two")],
+            sort_key: SortKey::Current(Stat::Bravery),
+            sort_descending: true,
+        };
+        save_config(&path, &config).unwrap();
+        assert_eq!(load_config(&path).unwrap(), config);
+
+        // Settings the file doesn't mention fall back to their defaults
+        fs::write(&path, "sort_descending = true\n").unwrap();
+        let partial = load_config(&path).unwrap();
+        assert!(partial.sort_descending);
+        assert_eq!(partial.game_dir, None);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
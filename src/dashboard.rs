@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::rank::Ranks;
+use crate::save::Save;
+use crate::soldier::{Gender, Soldier, Stat};
+
+/// How many soldiers are listed as the best at each stat
+const TOP_COUNT: usize = 5;
+
+/// Soldiers sharing something, such as a rank or a dropship, by ID in save order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group<K> {
+    pub key: K,
+    pub soldier_ids: Vec<u32>,
+}
+
+/// One soldier's value for a stat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatValue {
+    pub soldier_id: u32,
+    pub value: u32,
+}
+
+/// The spread of one stat's current values across the roster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatSummary {
+    pub stat: Stat,
+    pub average: f32,
+    /// The lowest value, with the first soldier who has it
+    pub min: StatValue,
+    /// The highest value, with the first soldier who has it
+    pub max: StatValue,
+    /// The best soldiers at this stat, best first
+    pub top: Vec<StatValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub headcount: usize,
+    /// Soldiers assigned to a dropship, rather than in reserve at the base
+    pub assigned: usize,
+    pub stats: Vec<StatSummary>,
+    /// Ranks from lowest to highest, including ones nobody holds
+    pub ranks: Vec<Group<String>>,
+    /// Keyed by flag
+    pub nationalities: Vec<Group<String>>,
+    pub genders: Vec<Group<Gender>>,
+    /// Keyed by dropship name
+    pub dropships: Vec<Group<String>>,
+}
+
+pub fn summarise(save: &Save, ranks: &Ranks) -> Summary {
+    let soldiers = &save.soldiers;
+    let stat_summaries = if soldiers.is_empty() {
+        vec![]
+    } else {
+        Stat::ALL
+            .into_iter()
+            .map(|stat| {
+                let mut values: Vec<_> = soldiers
+                    .iter()
+                    .map(|soldier| StatValue {
+                        soldier_id: soldier.id,
+                        value: soldier.stats.current(stat),
+                    })
+                    .collect();
+                // Wide enough for any number of soldiers with any values the file can hold
+                let total: u64 = values.iter().map(|value| u64::from(value.value)).sum();
+                let min = *values.iter().min_by_key(|value| value.value).unwrap();
+                // Stable, so ties go to whoever comes first in the save
+                values.sort_by_key(|value| Reverse(value.value));
+                let max = values[0];
+                StatSummary {
+                    stat,
+                    average: total as f32 / values.len() as f32,
+                    min,
+                    max,
+                    top: values.into_iter().take(TOP_COUNT).collect(),
+                }
+            })
+            .collect()
+    };
+
+    let rank_groups = ranks
+        .all()
+        .iter()
+        .map(|rank| Group {
+            key: rank.name.clone(),
+            soldier_ids: soldiers
+                .iter()
+                .filter(|soldier| ranks.rank_for(soldier.xp) == Some(rank))
+                .map(|soldier| soldier.id)
+                .collect(),
+        })
+        .collect();
+
+    let group_by = |key: &dyn Fn(&Soldier) -> String| {
+        let mut groups: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for soldier in soldiers {
+            groups.entry(key(soldier)).or_default().push(soldier.id);
+        }
+        groups
+            .into_iter()
+            .map(|(key, soldier_ids)| Group { key, soldier_ids })
+            .collect::<Vec<_>>()
+    };
+    let nationalities = group_by(&|soldier| String::from_utf8_lossy(&soldier.nation).to_string());
+    let dropships = group_by(&|soldier| String::from_utf8_lossy(&soldier.carrier).to_string())
+        .into_iter()
+        .filter(|group| !group.key.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut unknown_genders: Vec<_> = soldiers
+        .iter()
+        .map(|soldier| soldier.gender)
+        .filter(|gender| matches!(gender, Gender::Unknown(_)))
+        .collect();
+    unknown_genders.sort_by_key(|gender| gender.to_byte());
+    unknown_genders.dedup();
+    let genders = [Gender::Male, Gender::Female]
+        .into_iter()
+        .chain(unknown_genders)
+        .map(|gender| Group {
+            key: gender,
+            soldier_ids: soldiers
+                .iter()
+                .filter(|soldier| soldier.gender == gender)
+                .map(|soldier| soldier.id)
+                .collect(),
+        })
+        .collect();
+
+    Summary {
+        headcount: soldiers.len(),
+        assigned: dropships.iter().map(|group| group.soldier_ids.len()).sum(),
+        stats: stat_summaries,
+        ranks: rank_groups,
+        nationalities,
+        genders,
+        dropships,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::load_save;
+
+    #[test]
+    fn it_summarises_full_save() {
+        let save = load_save("full_save.sav");
+        let summary = summarise(&save, &Ranks::default());
+
+        assert_eq!(summary.headcount, 22);
+        let mut huge = save.clone();
+        for soldier in &mut huge.soldiers {
+            soldier.stats.set_current(Stat::Bravery, u32::MAX);
+        }
+        assert_eq!(
+            summarise(&huge, &Ranks::default()).stats[5].average,
+            u32::MAX as f32
+        );
+        assert_eq!(summary.assigned, 8);
+        assert_eq!(
+            summary.dropships,
+            vec![Group {
+                key: String::from("Charlie - 1/13"),
+                soldier_ids: vec![16, 18, 19, 20, 21, 23, 24, 25],
+            }]
+        );
+        assert_eq!(
+            summary
+                .ranks
+                .iter()
+                .map(|group| group.soldier_ids.len())
+                .sum::<usize>(),
+            22
+        );
+
+        let bravery = &summary.stats[5];
+        assert_eq!(bravery.stat, Stat::Bravery);
+        assert_eq!(bravery.top.len(), 5);
+        assert_eq!(bravery.top[0], bravery.max);
+        assert!(
+            bravery.min.value as f32 <= bravery.average
+                && bravery.average <= bravery.max.value as f32
+        );
+        assert_eq!(
+            save.get_soldier(bravery.max.soldier_id)
+                .unwrap()
+                .stats
+                .current(Stat::Bravery),
+            bravery.max.value
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use crate::inspect::UNDECODED_PARTS;
+use crate::save::Save;
+use crate::soldier::{part_kind, PartKind, Soldier};
+
+/// Something about a soldier that differs between two saves.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A field the editor understands, with its value before and after
+    Field {
+        name: &'static str,
+        before: String,
+        after: String,
+    },
+    /// A byte nobody has decoded yet, by where it is in its part. A part that grew or shrank
+    /// has bytes on only one side.
+    Byte {
+        part: &'static str,
+        offset: usize,
+        before: Option<u8>,
+        after: Option<u8>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let byte = |byte: &Option<u8>| byte.map(|byte| byte.to_string()).unwrap_or("–".into());
+        match self {
+            Change::Field {
+                name,
+                before,
+                after,
+            } => write!(f, "{}: {} → {}", name.to_lowercase(), before, after),
+            Change::Byte {
+                part,
+                offset,
+                before,
+                after,
+            } => write!(
+                f,
+                "{} byte {}: {} → {}",
+                part.to_lowercase(),
+                offset,
+                byte(before),
+                byte(after)
+            ),
+        }
+    }
+}
+
+/// The changes to one soldier who is in both saves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoldierDiff {
+    pub id: u32,
+    /// As of the later save
+    pub name: String,
+    pub changes: Vec<Change>,
+}
+
+impl SoldierDiff {
+    /// Each change on its own line, like `soldier 23: unknown block byte 8: 2 → 3`.
+    pub fn lines(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .map(|change| format!("soldier {}: {}", self.id, change))
+            .collect()
+    }
+}
+
+/// How the soldiers in one save differ from those in another, matched up by ID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveDiff {
+    /// Soldiers only in the later save
+    pub added: Vec<u32>,
+    /// Soldiers only in the earlier save
+    pub removed: Vec<u32>,
+    /// Soldiers in both saves with something different, in the later save's order
+    pub changed: Vec<SoldierDiff>,
+}
+
+impl SaveDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+pub fn diff_saves(before: &Save, after: &Save) -> SaveDiff {
+    let mut diff = SaveDiff::default();
+    for soldier in &after.soldiers {
+        match before.get_soldier(soldier.id) {
+            Some(earlier) => {
+                let changes = diff_soldiers(earlier, soldier);
+                if !changes.is_empty() {
+                    diff.changed.push(SoldierDiff {
+                        id: soldier.id,
+                        name: soldier.name.clone(),
+                        changes,
+                    });
+                }
+            }
+            None => diff.added.push(soldier.id),
+        }
+    }
+    diff.removed = before
+        .soldiers
+        .iter()
+        .map(|soldier| soldier.id)
+        .filter(|id| after.get_soldier(*id).is_none())
+        .collect();
+    diff
+}
+
+/// Compares two versions of a soldier part by part, in the order they're stored.
+pub fn diff_soldiers(before: &Soldier, after: &Soldier) -> Vec<Change> {
+    let mut changes = vec![];
+    for ((name, before), (_, after)) in before.record_parts().into_iter().zip(after.record_parts())
+    {
+        // Lengths change along with the text they're the length of
+        if before == after || name.ends_with(" length") {
+            continue;
+        }
+        if UNDECODED_PARTS.contains(&name) {
+            changes.extend(
+                (0..before.len().max(after.len()))
+                    .filter(|offset| before.get(*offset) != after.get(*offset))
+                    .map(|offset| Change::Byte {
+                        part: name,
+                        offset,
+                        before: before.get(offset).copied(),
+                        after: after.get(offset).copied(),
+                    }),
+            );
+        } else {
+            changes.push(Change::Field {
+                name,
+                before: describe(name, &before),
+                after: describe(name, &after),
+            });
+        }
+    }
+    changes
+}
+
+/// Shows a decoded part's value the way it's stored: numbers as numbers and text as text.
+fn describe(name: &str, bytes: &[u8]) -> String {
+    let word = <[u8; 4]>::try_from(bytes);
+    match (part_kind(name), word) {
+        (PartKind::Text, _) => format!("\"{}\"", String::from_utf8_lossy(bytes)),
+        (PartKind::U32, Ok(word)) => u32::from_le_bytes(word).to_string(),
+        (PartKind::F32, Ok(word)) => f32::from_le_bytes(word).to_string(),
+        (PartKind::U8, _) if bytes.len() == 1 => bytes[0].to_string(),
+        _ => bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soldier::{parse_soldier, parse_soldier_with_spans, Stat};
+    use crate::testutil::{load_save, read_fixture};
+
+    #[test]
+    fn it_diffs_saves_by_soldier_id() {
+        let before = load_save("full_save.sav");
+        assert!(diff_saves(&before, &before).is_empty());
+
+        // A mission later: one soldier is hurt and gains XP, another is lost and one is hired
+        let mut after = before.clone();
+        let hurt = &before.soldiers[0];
+        let (_, (_, spans)) = parse_soldier_with_spans(&hurt.serialise()).unwrap();
+        let span = |field| {
+            spans
+                .iter()
+                .find(|(name, _)| *name == field)
+                .unwrap()
+                .1
+                .clone()
+        };
+        let mut record = hurt.serialise();
+        let unknown = record[span("Unknown block").start + 8];
+        record[span("Unknown block").start + 8] = unknown.wrapping_add(1);
+        record.insert(span("End marker").start, 7);
+        let (_, mut soldier) = parse_soldier(&record).unwrap();
+        soldier.xp += 10;
+        soldier
+            .stats
+            .set_current(Stat::Health, hurt.stats.current(Stat::Health) - 5);
+        soldier.name.push_str(" Jr");
+        after.soldiers[0] = soldier;
+        let lost = after.soldiers.remove(1).id;
+        let recruited = after.import_soldier(after.soldiers[2].clone());
+
+        let diff = diff_saves(&before, &after);
+        assert_eq!(diff.added, vec![recruited]);
+        assert_eq!(diff.removed, vec![lost]);
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed[0];
+        assert_eq!(changed.id, hurt.id);
+        assert_eq!(
+            changed.lines(),
+            vec![
+                format!("soldier {}: name: \"{1}\" → \"{1} Jr\"", hurt.id, hurt.name),
+                format!(
+                    "soldier {}: health: {} → {}",
+                    hurt.id,
+                    hurt.stats.current(Stat::Health),
+                    hurt.stats.current(Stat::Health) - 5
+                ),
+                format!("soldier {}: xp: {} → {}", hurt.id, hurt.xp, hurt.xp + 10),
+                format!(
+                    "soldier {}: unknown block byte 8: {} → {}",
+                    hurt.id,
+                    unknown,
+                    unknown.wrapping_add(1)
+                ),
+                format!(
+                    "soldier {}: remaining bytes byte {}: – → 7",
+                    hurt.id,
+                    span("Remaining bytes").len()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_shows_text_as_text_whatever_its_length() {
+        let file = read_fixture("single_soldier.sav");
+        let (_, before) = parse_soldier(&file).unwrap();
+        let mut after = before.clone();
+        after.nation = b"ussr".to_vec();
+        after.name = String::from("Ruri");
+        after.race = b"a".to_vec();
+        after.age += 1.0;
+
+        let changes: Vec<_> = diff_soldiers(&before, &after)
+            .iter()
+            .map(Change::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "name: \"Ruri Yasuda\" → \"Ruri\"",
+                "race: \"asi\" → \"a\"",
+                "flag: \"japan\" → \"ussr\"",
+                &format!("age: {} → {}", before.age, after.age),
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+use crate::names::{self, NameLists};
+use crate::portraits::{self, Portraits};
+use crate::rank::{self, Ranks};
+use crate::strings::{self, StringTable};
+use crate::validate::Rules;
+
+// Relative to the game's install directory
+const NATIONALITIES_FILE: &str = "assets/nationalities.xml";
+
+/// A nationality recruits can be rolled from, as defined by the game.
+///
+/// Each `<nationality>` element carries `name` and `flag` attributes, with the regiments,
+/// experience backgrounds and races it can roll as child elements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Nationality {
+    pub name: String,
+    pub flag: String,
+    pub races: Vec<String>,
+    pub regiments: Vec<String>,
+    pub experiences: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GameData {
+    pub nationalities: Vec<Nationality>,
+    pub strings: StringTable,
+    pub portraits: Portraits,
+    pub ranks: Ranks,
+    pub names: NameLists,
+}
+
+impl GameData {
+    pub fn flags(&self) -> BTreeSet<String> {
+        self.nationalities
+            .iter()
+            .map(|nationality| nationality.flag.clone())
+            .collect()
+    }
+
+    pub fn races(&self) -> BTreeSet<String> {
+        self.nationalities
+            .iter()
+            .flat_map(|nationality| nationality.races.iter().cloned())
+            .collect()
+    }
+
+    pub fn regiments(&self) -> BTreeSet<String> {
+        self.nationalities
+            .iter()
+            .flat_map(|nationality| nationality.regiments.iter().cloned())
+            .collect()
+    }
+
+    pub fn experiences(&self) -> BTreeSet<String> {
+        self.nationalities
+            .iter()
+            .flat_map(|nationality| nationality.experiences.iter().cloned())
+            .collect()
+    }
+
+    pub fn get_nationality(&self, flag: &str) -> Option<&Nationality> {
+        self.nationalities
+            .iter()
+            .find(|nationality| nationality.flag == flag)
+    }
+
+    /// Validation rules with the lookup tables filled in from the game data.
+    pub fn rules(&self) -> Rules {
+        let mut rules = Rules {
+            face_counts: self.portraits.face_counts(),
+            ..Rules::default()
+        };
+        if !self.nationalities.is_empty() {
+            rules.nations = Some(self.flags());
+            rules.regiments = Some(self.regiments());
+            rules.experiences = Some(self.experiences());
+        }
+        rules
+    }
+}
+
+pub fn load_game_data(install_dir: &Path) -> Result<GameData, Box<dyn Error>> {
+    let xml = fs::read_to_string(install_dir.join(NATIONALITIES_FILE))?;
+    let nationalities = parse_nationalities(&xml)?;
+    let strings = strings::load_string_tables(install_dir)?;
+    let portraits = portraits::scan_portraits(&[install_dir.to_path_buf()]);
+    let ranks = rank::load_ranks(&[install_dir.to_path_buf()])?.unwrap_or_default();
+    let names = names::load_name_lists(&[install_dir.to_path_buf()])?;
+    Result::Ok(GameData {
+        nationalities,
+        strings,
+        portraits,
+        ranks,
+        names,
+    })
+}
+
+pub fn parse_nationalities(xml: &str) -> Result<Vec<Nationality>, roxmltree::Error> {
+    let document = Document::parse(xml)?;
+    Result::Ok(
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name("nationality"))
+            .map(|node| Nationality {
+                name: node.attribute("name").unwrap_or_default().to_string(),
+                flag: node.attribute("flag").unwrap_or_default().to_string(),
+                races: child_texts(node, "race"),
+                regiments: child_texts(node, "regiment"),
+                experiences: child_texts(node, "experience"),
+            })
+            .filter(|nationality| !nationality.flag.is_empty())
+            .collect(),
+    )
+}
+
+fn child_texts(node: Node, tag_name: &str) -> Vec<String> {
+    node.children()
+        .filter(|child| child.has_tag_name(tag_name))
+        .filter_map(|child| child.text())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NATIONALITIES: &str = r#"
+        <nationalities>
+            <nationality name="Japanese" flag="japan">
+                <race>asi</race>
+                <regiment>regiment.japan1</regiment>
+                <regiment>regiment.japan2</regiment>
+                <experience>experience.none</experience>
+            </nationality>
+            <nationality name="Soviet Union" flag="ussr">
+                <race>nor</race>
+                <race>asi</race>
+                <regiment>regiment.russian1</regiment>
+                <experience>experience.none</experience>
+                <experience>experience.russian1</experience>
+            </nationality>
+            <nationality name="Broken" />
+        </nationalities>
+    "#;
+
+    #[test]
+    fn it_parses_nationalities() {
+        let nationalities = parse_nationalities(NATIONALITIES).unwrap();
+        assert_eq!(nationalities.len(), 2);
+        assert_eq!(
+            nationalities[0],
+            Nationality {
+                name: String::from("Japanese"),
+                flag: String::from("japan"),
+                races: vec![String::from("asi")],
+                regiments: vec![
+                    String::from("regiment.japan1"),
+                    String::from("regiment.japan2")
+                ],
+                experiences: vec![String::from("experience.none")],
+            }
+        );
+    }
+
+    #[test]
+    fn it_collects_keys() {
+        let game_data = GameData {
+            nationalities: parse_nationalities(NATIONALITIES).unwrap(),
+            ..GameData::default()
+        };
+        assert_eq!(
+            game_data.races().into_iter().collect::<Vec<_>>(),
+            vec!["asi", "nor"]
+        );
+        assert_eq!(
+            game_data.experiences().into_iter().collect::<Vec<_>>(),
+            vec!["experience.none", "experience.russian1"]
+        );
+        assert_eq!(
+            game_data.get_nationality("ussr").unwrap().name,
+            "Soviet Union"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::gamedata::{GameData, Nationality};
+use crate::names::NameLists;
+use crate::save::Save;
+use crate::soldier::{Gender, Soldier, SoldierStats};
+
+// The game data doesn't say how recruits are rolled, so these are approximations from the
+// recruits and soldiers in the sample saves, whose starting stats are all 35 to 68, whose ages
+// are mostly 21 to 35 and of whom about a third are women.
+/// Every starting stat is rolled uniformly from this range
+const STAT_RANGE: RangeInclusive<u32> = 35..=70;
+const AGE_RANGE: RangeInclusive<u32> = 21..=35;
+const FEMALE_CHANCE: f64 = 0.3;
+/// Faces to pick from when no portraits have been found for a race and gender
+const DEFAULT_FACE_COUNT: u32 = 10;
+
+/// Everything a recruit's identity is picked from.
+#[derive(Debug, Clone)]
+pub struct RecruitPool {
+    pub nationalities: Vec<Nationality>,
+    pub names: NameLists,
+    pub face_counts: HashMap<(String, Gender), u32>,
+}
+
+impl RecruitPool {
+    /// Uses the game's nationalities when they have been loaded, and otherwise the ones already
+    /// serving in the save.
+    pub fn new(game_data: &GameData, save: &Save) -> Self {
+        let nationalities = if game_data.nationalities.is_empty() {
+            nationalities_from_save(save)
+        } else {
+            game_data.nationalities.clone()
+        };
+        RecruitPool {
+            nationalities,
+            names: game_data.names.clone(),
+            face_counts: game_data.portraits.face_counts(),
+        }
+    }
+}
+
+/// Works out the nationalities a save's soldiers were recruited from, for when the game data
+/// isn't available.
+pub fn nationalities_from_save(save: &Save) -> Vec<Nationality> {
+    let mut nationalities: BTreeMap<String, Nationality> = BTreeMap::new();
+    for soldier in &save.soldiers {
+        let flag = String::from_utf8_lossy(&soldier.nation).to_string();
+        let nationality = nationalities
+            .entry(flag.clone())
+            .or_insert_with(|| Nationality {
+                name: soldier.nationality.clone(),
+                flag,
+                ..Nationality::default()
+            });
+        for (keys, value) in [
+            (&mut nationality.races, &soldier.race),
+            (&mut nationality.regiments, &soldier.regiment),
+            (&mut nationality.experiences, &soldier.experience),
+        ] {
+            let value = String::from_utf8_lossy(value).to_string();
+            if !keys.contains(&value) {
+                keys.push(value);
+            }
+        }
+    }
+    nationalities.into_values().collect()
+}
+
+/// Rolls a new identity and starting stats for a soldier, keeping their ID, XP, dropship and
+/// loadout.
+pub fn reroll(soldier: &mut Soldier, pool: &RecruitPool, rng: &mut impl Rng) {
+    let gender = if rng.gen_bool(FEMALE_CHANCE) {
+        Gender::Female
+    } else {
+        Gender::Male
+    };
+    soldier.gender = gender;
+
+    if let Some(nationality) = pool.nationalities.choose(rng) {
+        soldier.nationality = nationality.name.clone();
+        soldier.nation = nationality.flag.clone().into_bytes();
+        for (keys, value) in [
+            (&nationality.races, &mut soldier.race),
+            (&nationality.regiments, &mut soldier.regiment),
+            (&nationality.experiences, &mut soldier.experience),
+        ] {
+            if let Some(key) = keys.choose(rng) {
+                *value = key.clone().into_bytes();
+            }
+        }
+    }
+
+    let race = String::from_utf8_lossy(&soldier.race).to_string();
+    let face_count = pool
+        .face_counts
+        .get(&(race, gender))
+        .copied()
+        .unwrap_or(DEFAULT_FACE_COUNT);
+    soldier.face_number = rng.gen_range(0..face_count.max(1));
+
+    soldier.name = pool
+        .names
+        .generate(&String::from_utf8_lossy(&soldier.nation), gender, rng);
+    soldier.age = rng.gen_range(AGE_RANGE) as f32;
+    soldier.stats = roll_stats(rng);
+}
+
+/// A fresh recruit like those the game offers for hire, who isn't assigned to a dropship yet.
+pub fn recruit(id: u32, pool: &RecruitPool, rng: &mut impl Rng) -> Soldier {
+    let mut soldier = Soldier::recruit(id);
+    reroll(&mut soldier, pool, rng);
+    soldier
+}
+
+fn roll_stats(rng: &mut impl Rng) -> SoldierStats {
+    SoldierStats::new([(); 6].map(|_| rng.gen_range(STAT_RANGE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::inspect::UNDECODED_PARTS;
+    use crate::soldier::Stat;
+    use crate::testutil::load_save;
+    use crate::validate::{validate_with, Rules};
+
+    #[test]
+    fn it_finds_nationalities_in_save() {
+        let save = load_save("full_save.sav");
+        let nationalities = nationalities_from_save(&save);
+        let ussr = nationalities
+            .iter()
+            .find(|nationality| nationality.flag == "ussr")
+            .unwrap();
+        assert_eq!(ussr.name, "Soviet Union");
+        assert_eq!(ussr.races, vec!["nor", "asi"]);
+        assert_eq!(
+            ussr.regiments,
+            vec![
+                "regiment.russian3",
+                "regiment.russian2",
+                "regiment.russian1"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_recruits_reproducibly() {
+        let save = load_save("full_save.sav");
+        let pool = RecruitPool::new(&GameData::default(), &save);
+
+        let first = recruit(100, &pool, &mut StdRng::seed_from_u64(7));
+        let second = recruit(100, &pool, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first.serialise(), second.serialise());
+        assert_eq!(first.id, 100);
+        assert_eq!(first.xp, 0);
+        assert_eq!(first.dropship_seat, None);
+
+        // The bytes nobody has decoded are as they are for the recruits the game offers
+        let undecoded = |soldier: &Soldier| {
+            soldier
+                .record_parts()
+                .into_iter()
+                .filter(|(name, _)| UNDECODED_PARTS.contains(name))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(undecoded(&first), undecoded(&save.soldiers[0]));
+    }
+
+    #[test]
+    fn it_rerolls_valid_soldiers() {
+        let mut save = load_save("full_save.sav");
+        let pool = RecruitPool::new(&GameData::default(), &save);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for soldier in save.soldiers.iter_mut() {
+            let (id, carrier) = (soldier.id, soldier.carrier.clone());
+            reroll(soldier, &pool, &mut rng);
+            assert_eq!(soldier.id, id);
+            assert_eq!(soldier.carrier, carrier);
+            assert!(STAT_RANGE.contains(&soldier.stats.current(Stat::Bravery)));
+        }
+        assert_eq!(validate_with(&save, &Rules::default()), vec![]);
+    }
+}
@@ -0,0 +1,126 @@
+use std::ops::Range;
+
+use crate::soldier::Soldier;
+
+/// The parts of a soldier's record nobody has worked out yet
+pub const UNDECODED_PARTS: [&str; 4] = [
+    "Unknown block",
+    "Another unknown block",
+    "Unknown stat",
+    "Remaining bytes",
+];
+
+/// A named run of bytes in a soldier's record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub name: &'static str,
+    pub range: Range<usize>,
+    /// Whether the editor knows what the bytes mean
+    pub decoded: bool,
+}
+
+/// A soldier's record as it's stored in the save, with where each of its parts is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub bytes: Vec<u8>,
+    pub parts: Vec<Part>,
+}
+
+impl Record {
+    pub fn new(soldier: &Soldier) -> Self {
+        let mut bytes = vec![];
+        let mut parts = vec![];
+        for (name, part) in soldier.record_parts() {
+            // Empty strings take no room, so there's nothing to show for them
+            if !part.is_empty() {
+                parts.push(Part {
+                    name,
+                    range: bytes.len()..bytes.len() + part.len(),
+                    decoded: !UNDECODED_PARTS.contains(&name),
+                });
+            }
+            bytes.extend(part);
+        }
+        Record { bytes, parts }
+    }
+
+    /// The part a byte belongs to.
+    pub fn part_at(&self, offset: usize) -> Option<&Part> {
+        self.parts.iter().find(|part| part.range.contains(&offset))
+    }
+}
+
+/// Ways of reading the bytes starting at an offset, for guessing at what undecoded bytes are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub u32: Option<u32>,
+    pub f32: Option<f32>,
+    /// The rest of the part as text, up to the first unprintable byte
+    pub text: String,
+}
+
+pub fn read_at(record: &Record, offset: usize) -> Reading {
+    let word: Option<[u8; 4]> = record
+        .bytes
+        .get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok());
+    let end = record
+        .part_at(offset)
+        .map(|part| part.range.end)
+        .unwrap_or(offset);
+    let text = record.bytes[offset.min(end)..end]
+        .iter()
+        .take_while(|byte| byte.is_ascii_graphic() || **byte == b' ')
+        .map(|byte| *byte as char)
+        .collect();
+    Reading {
+        u32: word.map(u32::from_le_bytes),
+        f32: word.map(f32::from_le_bytes),
+        text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soldier::parse_soldier;
+    use crate::testutil::read_fixture;
+
+    #[test]
+    fn it_maps_soldier_record() {
+        let file = read_fixture("single_soldier.sav");
+        let (_, soldier) = parse_soldier(&file).unwrap();
+        let record = Record::new(&soldier);
+
+        assert_eq!(record.bytes, file);
+        assert_eq!(record.parts.first().unwrap().range.start, 0);
+        assert_eq!(record.parts.last().unwrap().range.end, file.len());
+        assert!(record
+            .parts
+            .windows(2)
+            .all(|pair| pair[0].range.end == pair[1].range.start));
+
+        let name = record
+            .parts
+            .iter()
+            .find(|part| part.name == "Name")
+            .unwrap();
+        assert!(name.decoded);
+        assert_eq!(&file[name.range.clone()], b"Ruri Yasuda");
+        let reading = read_at(&record, name.range.start + 5);
+        assert_eq!(reading.text, "Yasuda");
+
+        let id = record.part_at(15).unwrap();
+        assert_eq!(id.name, "ID");
+        assert_eq!(read_at(&record, 15).u32, Some(23));
+
+        let unknown = record
+            .parts
+            .iter()
+            .find(|part| part.name == "Unknown block")
+            .unwrap();
+        assert!(!unknown.decoded);
+        assert_eq!(unknown.range.len(), 36);
+        assert_eq!(read_at(&record, file.len() - 2).u32, None);
+    }
+}
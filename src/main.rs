@@ -1,6 +1,25 @@
+mod browser;
+mod charts;
+mod compare;
+mod config;
+mod dashboard;
+mod diff;
+mod gamedata;
+mod generator;
+mod inspect;
+mod names;
+mod portraits;
+mod rank;
+mod roster;
 mod save;
+mod savedirs;
 mod soldier;
+mod strings;
+#[cfg(test)]
+mod testutil;
+mod validate;
 mod view;
+mod watch;
 
 fn main() -> iced::Result {
     view::run()
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use roxmltree::Document;
+
+use crate::gamedata::Nationality;
+use crate::soldier::{Gender, Soldier};
+
+// Relative to the game's install directory, or to the root of a mod
+const NAMES_DIR: &str = "assets/names";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NameList {
+    pub male: Vec<String>,
+    pub female: Vec<String>,
+    pub surnames: Vec<String>,
+}
+
+impl NameList {
+    fn new(male: &[&str], female: &[&str], surnames: &[&str]) -> Self {
+        let owned = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        NameList {
+            male: owned(male),
+            female: owned(female),
+            surnames: owned(surnames),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        !self.male.is_empty() && !self.female.is_empty() && !self.surnames.is_empty()
+    }
+}
+
+/// First and last names for each nation, keyed by flag.
+#[derive(Debug, Clone)]
+pub struct NameLists {
+    lists: HashMap<String, NameList>,
+    /// Used for nations without a list of their own
+    fallback: NameList,
+}
+
+impl NameLists {
+    pub fn get(&self, flag: &str) -> &NameList {
+        self.lists
+            .get(flag)
+            .filter(|list| list.is_complete())
+            .unwrap_or(&self.fallback)
+    }
+
+    /// A random name for a soldier of the given nation, e.g. `Hiroshi Takada`.
+    pub fn generate(&self, flag: &str, gender: Gender, rng: &mut impl Rng) -> String {
+        let list = self.get(flag);
+        let first_names: Vec<_> = match gender {
+            Gender::Female => list.female.iter().collect(),
+            Gender::Male => list.male.iter().collect(),
+            Gender::Unknown(_) => list.female.iter().chain(&list.male).collect(),
+        };
+        format!(
+            "{} {}",
+            first_names
+                .choose(rng)
+                .map(|name| name.as_str())
+                .unwrap_or(""),
+            list.surnames.choose(rng).map(String::as_str).unwrap_or("")
+        )
+    }
+}
+
+impl Default for NameLists {
+    fn default() -> Self {
+        let lists = [
+            (
+                "japan",
+                NameList::new(
+                    &[
+                        "Hiroshi", "Kenji", "Masao", "Kiyoshi", "Takeshi", "Yuki", "Daisuke",
+                    ],
+                    &["Nao", "Ruri", "Yoko", "Akiko", "Emi", "Haruka", "Keiko"],
+                    &[
+                        "Takada", "Mori", "Uchida", "Yasuda", "Sato", "Suzuki", "Tanaka",
+                    ],
+                ),
+            ),
+            (
+                "ussr",
+                NameList::new(
+                    &[
+                        "Artur", "Sergey", "Dmitri", "Artem", "Ivan", "Mikhail", "Oleg",
+                    ],
+                    &[
+                        "Anna", "Olga", "Tatiana", "Irina", "Natalia", "Yelena", "Svetlana",
+                    ],
+                    &[
+                        "Pyatko", "Smirnov", "Volkov", "Bogdanov", "Ivanov", "Petrov",
+                    ],
+                ),
+            ),
+            (
+                "usa",
+                NameList::new(
+                    &["Alston", "James", "Robert", "Michael", "William", "David"],
+                    &["Mary", "Patricia", "Linda", "Barbara", "Susan", "Karen"],
+                    &["Davis", "Smith", "Johnson", "Williams", "Brown", "Jones"],
+                ),
+            ),
+            (
+                "uk",
+                NameList::new(
+                    &["Philip", "John", "George", "Thomas", "Edward", "Richard"],
+                    &["Margaret", "Elizabeth", "Sarah", "Helen", "Jane", "Alice"],
+                    &["Fisher", "Taylor", "Wright", "Walker", "Hughes", "Clarke"],
+                ),
+            ),
+            (
+                "france",
+                NameList::new(
+                    &[
+                        "Florian", "Pierre", "Jacques", "Michel", "Alain", "Philippe",
+                    ],
+                    &[
+                        "Marie", "Sophie", "Claire", "Isabelle", "Nathalie", "Juliette",
+                    ],
+                    &["Duval", "Martin", "Bernard", "Dubois", "Moreau", "Laurent"],
+                ),
+            ),
+            (
+                "spain",
+                NameList::new(
+                    &["Alvaro", "Miguel", "Javier", "Carlos", "Antonio", "Jose"],
+                    &["Isabel", "Carmen", "Lucia", "Maria", "Elena", "Pilar"],
+                    &["Ramos", "Perez", "Fernandez", "Garcia", "Lopez", "Sanchez"],
+                ),
+            ),
+            (
+                "italy",
+                NameList::new(
+                    &["Giovanni", "Sergio", "Marco", "Luca", "Paolo", "Franco"],
+                    &["Giulia", "Francesca", "Chiara", "Elena", "Sara", "Laura"],
+                    &["Sabatini", "Ricci", "Rossi", "Russo", "Ferrari", "Esposito"],
+                ),
+            ),
+            (
+                "eastgermany",
+                NameList::new(
+                    &["Jens", "Uwe", "Frank", "Thomas", "Andreas", "Michael"],
+                    &["Jana", "Katrin", "Petra", "Sabine", "Anja", "Kerstin"],
+                    &[
+                        "Winkler", "Schulz", "Richter", "Krause", "Lehmann", "Kaiser",
+                    ],
+                ),
+            ),
+            (
+                "canada",
+                NameList::new(
+                    &["Liam", "Pierre", "Connor", "Jean", "Scott", "Paul"],
+                    &["Emma", "Chloe", "Sarah", "Isabelle", "Megan", "Claire"],
+                    &[
+                        "Gagne",
+                        "Tremblay",
+                        "Roy",
+                        "MacDonald",
+                        "Campbell",
+                        "Wilson",
+                    ],
+                ),
+            ),
+            (
+                "sweden",
+                NameList::new(
+                    &["Robin", "Erik", "Lars", "Anders", "Johan", "Nils"],
+                    &["Karin", "Ingrid", "Astrid", "Sofia", "Maja", "Elin"],
+                    &["Eklund", "Andersson", "Johansson", "Karlsson", "Nilsson"],
+                ),
+            ),
+            (
+                "denmark",
+                NameList::new(
+                    &["Norwyn", "Mads", "Rasmus", "Soren", "Henrik", "Jesper"],
+                    &["Mette", "Freja", "Ida", "Camilla", "Signe", "Lone"],
+                    &[
+                        "Schultze", "Jensen", "Nielsen", "Hansen", "Pedersen", "Larsen",
+                    ],
+                ),
+            ),
+            (
+                "greece",
+                NameList::new(
+                    &[
+                        "Nikolaos",
+                        "Georgios",
+                        "Dimitrios",
+                        "Konstantinos",
+                        "Ioannis",
+                    ],
+                    &["Sofia", "Maria", "Eleni", "Katerina", "Vasiliki", "Georgia"],
+                    &[
+                        "Angelis",
+                        "Papadopoulos",
+                        "Pappas",
+                        "Nikolaidis",
+                        "Georgiou",
+                    ],
+                ),
+            ),
+            (
+                "hungary",
+                NameList::new(
+                    &["Laszlo", "Istvan", "Gabor", "Zoltan", "Attila", "Ferenc"],
+                    &["Eva", "Katalin", "Zsofia", "Erzsebet", "Anna", "Judit"],
+                    &["Nagy", "Kovacs", "Toth", "Szabo", "Horvath", "Varga"],
+                ),
+            ),
+        ]
+        .into_iter()
+        .map(|(flag, list)| (flag.to_string(), list))
+        .collect();
+
+        NameLists {
+            lists,
+            fallback: NameList::new(
+                &["John", "Michael", "David", "Daniel", "Alex", "Chris"],
+                &["Anna", "Maria", "Laura", "Kate", "Sam", "Alex"],
+                &["Smith", "Miller", "Carter", "Morgan", "Baker", "Young"],
+            ),
+        }
+    }
+}
+
+/// Loads name files over the bundled lists. Each nation's names live in
+/// `assets/names/<flag>.xml` as `<male>`, `<female>` and `<surname>` elements, and a later
+/// folder's file replaces an earlier one for the same nation.
+pub fn load_name_lists(roots: &[PathBuf]) -> Result<NameLists, Box<dyn Error>> {
+    let mut names = NameLists::default();
+    for root in roots {
+        let Ok(entries) = fs::read_dir(root.join(NAMES_DIR)) else {
+            continue;
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Some(flag) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let list = parse_name_list(&fs::read_to_string(&path)?)?;
+            names.lists.insert(flag.to_string(), list);
+        }
+    }
+    Result::Ok(names)
+}
+
+pub fn parse_name_list(xml: &str) -> Result<NameList, roxmltree::Error> {
+    let document = Document::parse(xml)?;
+    let texts = |tag_name: &str| {
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name(tag_name))
+            .filter_map(|node| node.text())
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect()
+    };
+    Result::Ok(NameList {
+        male: texts("male"),
+        female: texts("female"),
+        surnames: texts("surname"),
+    })
+}
+
+/// A line from an imported list of names, optionally with the flag of the nation to move the
+/// soldier to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedName {
+    pub name: String,
+    pub flag: Option<String>,
+}
+
+/// Parses a plain text list of names, one per line as `Name` or `Name, flag`. Blank lines and
+/// lines starting with `#` are skipped.
+pub fn parse_name_file(text: &str) -> Vec<ImportedName> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.rsplit_once(',') {
+            Some((name, flag)) if !flag.trim().is_empty() => ImportedName {
+                name: name.trim().to_string(),
+                flag: Some(flag.trim().to_string()),
+            },
+            Some((name, _)) => ImportedName {
+                name: name.trim().to_string(),
+                flag: None,
+            },
+            None => ImportedName {
+                name: line.to_string(),
+                flag: None,
+            },
+        })
+        .collect()
+}
+
+/// Hands the imported names out to the soldiers in order, until either runs out. Names with a
+/// flag also set the soldier's nation, and their nationality when it is a known one. Returns how
+/// many soldiers were renamed.
+pub fn assign_names<'a>(
+    soldiers: impl IntoIterator<Item = &'a mut Soldier>,
+    names: &[ImportedName],
+    nationalities: &[Nationality],
+) -> usize {
+    let mut renamed = 0;
+    for (soldier, imported) in soldiers.into_iter().zip(names) {
+        soldier.name = imported.name.clone();
+        if let Some(flag) = &imported.flag {
+            soldier.nation = flag.clone().into_bytes();
+            if let Some(nationality) = nationalities
+                .iter()
+                .find(|nationality| &nationality.flag == flag)
+            {
+                soldier.nationality = nationality.name.clone();
+            }
+        }
+        renamed += 1;
+    }
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::generator::nationalities_from_save;
+    use crate::testutil::load_save;
+
+    #[test]
+    fn it_generates_names_for_nation() {
+        let names = NameLists::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        let list = names.get("japan");
+
+        let name = names.generate("japan", Gender::Female, &mut rng);
+        let (first, last) = name.split_once(' ').unwrap();
+        assert!(list.female.contains(&first.to_string()));
+        assert!(list.surnames.contains(&last.to_string()));
+
+        assert_eq!(names.get("atlantis"), &names.fallback);
+    }
+
+    #[test]
+    fn it_parses_name_list() {
+        let list = parse_name_list(
+            r#"<names>
+                <male>Kenji</male>
+                <female> Emi </female>
+                <surname>Mori</surname>
+                <surname>Sato</surname>
+            </names>"#,
+        )
+        .unwrap();
+        assert_eq!(list.male, vec!["Kenji"]);
+        assert_eq!(list.female, vec!["Emi"]);
+        assert_eq!(list.surnames, vec!["Mori", "Sato"]);
+        assert!(list.is_complete());
+    }
+
+    #[test]
+    fn it_imports_names_across_roster() {
+        let mut save = load_save("full_save.sav");
+        let nationalities = nationalities_from_save(&save);
+
+        let names = parse_name_file(
+            "# The team\nAda Lovelace, ussr\n\nAlan Turing\nGrace Hopper, atlantis\n",
+        );
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[1].flag, None);
+
+        let renamed = assign_names(save.soldiers.iter_mut(), &names, &nationalities);
+        assert_eq!(renamed, 3);
+        assert_eq!(save.soldiers[0].name, "Ada Lovelace");
+        assert_eq!(save.soldiers[0].nation, b"ussr");
+        assert_eq!(save.soldiers[0].nationality, "Soviet Union");
+        assert_eq!(save.soldiers[1].name, "Alan Turing");
+        assert_eq!(save.soldiers[2].nation, b"atlantis");
+        assert_ne!(save.soldiers[3].name, "Grace Hopper");
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::soldier::Gender;
+
+// Relative to the game's install directory, or to the root of a mod
+const PORTRAITS_DIR: &str = "assets/portraits";
+
+/// Portrait images for every race and gender, keyed by face number.
+///
+/// Portraits are laid out as `assets/portraits/<race>/<male|female>/<face number>.png`. Mods such
+/// as "khall's more portraits" use the same layout, so scanning them after the game adds their
+/// faces to the pool and replaces any the game ships with the same number.
+#[derive(Debug, Clone, Default)]
+pub struct Portraits {
+    faces: HashMap<(String, Gender), BTreeMap<u32, PathBuf>>,
+}
+
+impl Portraits {
+    pub fn get(&self, race: &str, gender: Gender, face_number: u32) -> Option<&Path> {
+        self.faces
+            .get(&(race.to_string(), gender))
+            .and_then(|faces| faces.get(&face_number))
+            .map(PathBuf::as_path)
+    }
+
+    pub fn faces(&self, race: &str, gender: Gender) -> impl Iterator<Item = (u32, &Path)> {
+        self.faces
+            .get(&(race.to_string(), gender))
+            .into_iter()
+            .flatten()
+            .map(|(face_number, path)| (*face_number, path.as_path()))
+    }
+
+    /// How many faces the game will pick from for each race and gender.
+    pub fn face_counts(&self) -> HashMap<(String, Gender), u32> {
+        self.faces
+            .iter()
+            .filter_map(|(key, faces)| {
+                faces
+                    .keys()
+                    .next_back()
+                    .map(|face_number| (key.clone(), face_number + 1))
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+}
+
+pub fn scan_portraits(roots: &[PathBuf]) -> Portraits {
+    let mut portraits = Portraits::default();
+    for root in roots {
+        let Ok(races) = fs::read_dir(root.join(PORTRAITS_DIR)) else {
+            continue;
+        };
+        for race_dir in races.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            let Some(race) = race_dir.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            for (gender_dir, gender) in [("male", Gender::Male), ("female", Gender::Female)] {
+                let Ok(files) = fs::read_dir(race_dir.join(gender_dir)) else {
+                    continue;
+                };
+                let faces = portraits
+                    .faces
+                    .entry((race.to_string(), gender))
+                    .or_default();
+                for path in files.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+                    if let Some(face_number) = parse_face_number(&path) {
+                        faces.insert(face_number, path);
+                    }
+                }
+            }
+        }
+    }
+    portraits.faces.retain(|_, faces| !faces.is_empty());
+    portraits
+}
+
+fn parse_face_number(path: &Path) -> Option<u32> {
+    if !path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+    {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn touch(root: &Path, file: &str) {
+        let path = root.join(PORTRAITS_DIR).join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, []).unwrap();
+    }
+
+    #[test]
+    fn it_scans_game_and_mod_portraits() {
+        let root = env::temp_dir().join(format!("portraits-{}", std::process::id()));
+        let game = root.join("game");
+        let mod_dir = root.join("mod");
+        touch(&game, "asi/female/0.png");
+        touch(&game, "asi/female/1.png");
+        touch(&game, "asi/female/notes.txt");
+        touch(&game, "nor/male/0.png");
+        touch(&mod_dir, "asi/female/1.png");
+        touch(&mod_dir, "asi/female/2.png");
+
+        let portraits = scan_portraits(&[game.clone(), mod_dir.clone()]);
+        let faces: Vec<_> = portraits.faces("asi", Gender::Female).collect();
+        assert_eq!(
+            faces,
+            vec![
+                (
+                    0,
+                    game.join(PORTRAITS_DIR).join("asi/female/0.png").as_path()
+                ),
+                (
+                    1,
+                    mod_dir
+                        .join(PORTRAITS_DIR)
+                        .join("asi/female/1.png")
+                        .as_path()
+                ),
+                (
+                    2,
+                    mod_dir
+                        .join(PORTRAITS_DIR)
+                        .join("asi/female/2.png")
+                        .as_path()
+                ),
+            ]
+        );
+        assert_eq!(
+            portraits.face_counts(),
+            HashMap::from([
+                ((String::from("asi"), Gender::Female), 3),
+                ((String::from("nor"), Gender::Male), 1),
+            ])
+        );
+        assert!(portraits.get("nor", Gender::Female, 0).is_none());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use roxmltree::Document;
+
+// Relative to the game's install directory, or to the root of a mod
+const RANKS_FILE: &str = "assets/ranks.xml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rank {
+    pub name: String,
+    pub insignia: String,
+    /// XP needed to be promoted to this rank
+    pub xp: u32,
+}
+
+impl std::fmt::Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The ranks a soldier is promoted through, ordered by XP threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranks(Vec<Rank>);
+
+impl Ranks {
+    pub fn new(mut ranks: Vec<Rank>) -> Self {
+        ranks.sort_by_key(|rank| rank.xp);
+        Ranks(ranks)
+    }
+
+    /// The highest rank the XP is enough for.
+    pub fn rank_for(&self, xp: u32) -> Option<&Rank> {
+        self.0.iter().rev().find(|rank| rank.xp <= xp)
+    }
+
+    pub fn all(&self) -> &[Rank] {
+        &self.0
+    }
+
+    /// The XP soldiers holding the rank have, up to what the next rank needs.
+    pub fn xp_range(&self, rank: &Rank) -> RangeInclusive<u32> {
+        let next = self.0.iter().find(|other| other.xp > rank.xp);
+        rank.xp..=next.map(|next| next.xp - 1).unwrap_or(u32::MAX)
+    }
+}
+
+impl Default for Ranks {
+    /// The unmodded game's ranks
+    fn default() -> Self {
+        let rank = |name: &str, insignia: &str, xp| Rank {
+            name: name.to_string(),
+            insignia: insignia.to_string(),
+            xp,
+        };
+        Ranks::new(vec![
+            rank("Rookie", "None", 0),
+            rank("Private", "One chevron", 5),
+            rank("Corporal", "Two chevrons", 15),
+            rank("Sergeant", "Three chevrons", 30),
+            rank("Lieutenant", "One bar", 50),
+            rank("Captain", "Two bars", 75),
+            rank("Major", "Oak leaf", 110),
+            rank("Colonel", "Eagle", 150),
+        ])
+    }
+}
+
+/// Loads the rank table from the last of the folders that has one, so mods that change the
+/// thresholds (such as XCE) override the game.
+pub fn load_ranks(roots: &[PathBuf]) -> Result<Option<Ranks>, Box<dyn Error>> {
+    let Some(path) = roots
+        .iter()
+        .rev()
+        .map(|root| root.join(RANKS_FILE))
+        .find(|path| path.is_file())
+    else {
+        return Result::Ok(None);
+    };
+    let ranks = parse_ranks(&fs::read_to_string(path)?)?;
+    Result::Ok(Some(ranks))
+}
+
+pub fn parse_ranks(xml: &str) -> Result<Ranks, Box<dyn Error>> {
+    let document = Document::parse(xml)?;
+    let mut ranks = vec![];
+    for node in document
+        .descendants()
+        .filter(|node| node.has_tag_name("rank"))
+    {
+        ranks.push(Rank {
+            name: node.attribute("name").unwrap_or_default().to_string(),
+            insignia: node.attribute("insignia").unwrap_or_default().to_string(),
+            xp: node.attribute("xp").unwrap_or("0").parse()?,
+        });
+    }
+    Result::Ok(Ranks::new(ranks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_rank_for_xp() {
+        let ranks = Ranks::default();
+        assert_eq!(ranks.rank_for(0).unwrap().name, "Rookie");
+        assert_eq!(ranks.rank_for(4).unwrap().name, "Rookie");
+        assert_eq!(ranks.rank_for(5).unwrap().name, "Private");
+        assert_eq!(ranks.rank_for(u32::MAX).unwrap().name, "Colonel");
+
+        let private = ranks.rank_for(5).unwrap();
+        assert_eq!(*ranks.xp_range(private).start(), 5);
+        let next = *ranks.xp_range(private).end() + 1;
+        assert_ne!(ranks.rank_for(next), Some(private));
+        assert_eq!(
+            *ranks.xp_range(ranks.rank_for(u32::MAX).unwrap()).end(),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn it_parses_ranks() {
+        let ranks = parse_ranks(
+            r#"<ranks>
+                <rank name="Specialist" insignia="Two chevrons" xp="20" />
+                <rank name="Recruit" insignia="None" xp="0" />
+            </ranks>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ranks.all().iter().map(|rank| rank.xp).collect::<Vec<_>>(),
+            vec![0, 20]
+        );
+        assert_eq!(ranks.rank_for(25).unwrap().insignia, "Two chevrons");
+        assert!(parse_ranks(r#"<ranks><rank name="Broken" xp="lots" /></ranks>"#).is_err());
+    }
+}
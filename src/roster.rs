@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::soldier::{Gender, Soldier, Stat};
+
+/// What the soldier list can be ordered by.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    /// The order soldiers are stored in the save, which is also the order they were hired in
+    #[default]
+    Id,
+    Name,
+    Nationality,
+    Gender,
+    Xp,
+    Age,
+    /// A stat's current value
+    Current(Stat),
+    /// The value a stat started at when the soldier was recruited
+    Base(Stat),
+}
+
+impl SortKey {
+    /// Every key, with the stats' current values and then their base values.
+    pub fn all() -> Vec<SortKey> {
+        [
+            SortKey::Id,
+            SortKey::Name,
+            SortKey::Nationality,
+            SortKey::Gender,
+            SortKey::Xp,
+            SortKey::Age,
+        ]
+        .into_iter()
+        .chain(Stat::ALL.map(SortKey::Current))
+        .chain(Stat::ALL.map(SortKey::Base))
+        .collect()
+    }
+
+    fn compare(&self, a: &Soldier, b: &Soldier) -> Ordering {
+        match self {
+            SortKey::Id => a.id.cmp(&b.id),
+            SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortKey::Nationality => a.nationality.cmp(&b.nationality),
+            SortKey::Gender => a.gender.to_byte().cmp(&b.gender.to_byte()),
+            SortKey::Xp => a.xp.cmp(&b.xp),
+            SortKey::Age => a.age.total_cmp(&b.age),
+            SortKey::Current(stat) => a.stats.current(*stat).cmp(&b.stats.current(*stat)),
+            SortKey::Base(stat) => a.stats.original(*stat).cmp(&b.stats.original(*stat)),
+        }
+    }
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Id => write!(f, "ID"),
+            SortKey::Name => write!(f, "Name"),
+            SortKey::Nationality => write!(f, "Nationality"),
+            SortKey::Gender => write!(f, "Gender"),
+            SortKey::Xp => write!(f, "XP"),
+            SortKey::Age => write!(f, "Age"),
+            SortKey::Current(stat) => write!(f, "{}", stat),
+            SortKey::Base(stat) => write!(f, "{} base", stat),
+        }
+    }
+}
+
+/// Which soldiers the roster shows. Unset filters let every soldier through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RosterFilter {
+    /// Matched case-insensitively against the name, nationality and regiment
+    pub search: String,
+    pub gender: Option<Gender>,
+    pub nation: Option<String>,
+    pub role: Option<String>,
+    /// Soldiers with this much XP, such as everyone holding a rank
+    pub xp: Option<RangeInclusive<u32>>,
+}
+
+impl RosterFilter {
+    pub fn matches(&self, soldier: &Soldier) -> bool {
+        let search = self.search.trim().to_lowercase();
+        let found = search.is_empty()
+            || [
+                soldier.name.clone(),
+                soldier.nationality.clone(),
+                String::from_utf8_lossy(&soldier.regiment).to_string(),
+            ]
+            .iter()
+            .any(|value| value.to_lowercase().contains(&search));
+
+        found
+            && self.gender.is_none_or(|gender| soldier.gender == gender)
+            && self
+                .nation
+                .as_ref()
+                .is_none_or(|nation| soldier.nation == nation.as_bytes())
+            && self
+                .role
+                .as_ref()
+                .is_none_or(|role| soldier.role().as_ref() == Some(role))
+            && self.xp.as_ref().is_none_or(|xp| xp.contains(&soldier.xp))
+    }
+}
+
+/// The soldiers the filter lets through, in the order picked.
+pub fn roster<'a>(
+    soldiers: &'a [Soldier],
+    filter: &RosterFilter,
+    sort_key: SortKey,
+    descending: bool,
+) -> Vec<&'a Soldier> {
+    let mut roster: Vec<_> = soldiers
+        .iter()
+        .filter(|soldier| filter.matches(soldier))
+        .collect();
+    // Stable, so ties stay in save order
+    roster.sort_by(|a, b| {
+        let ordering = sort_key.compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    roster
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rank::Ranks;
+    use crate::testutil::load_save;
+
+    #[test]
+    fn it_filters_roster() {
+        let save = load_save("full_save.sav");
+        assert_eq!(save.soldiers[0].role().as_deref(), Some("Rifleman"));
+
+        let everyone = roster(&save.soldiers, &RosterFilter::default(), SortKey::Id, false);
+        assert_eq!(everyone.len(), save.soldiers.len());
+
+        let filter = RosterFilter {
+            search: String::from("SOVIET"),
+            ..RosterFilter::default()
+        };
+        let soviets = roster(&save.soldiers, &filter, SortKey::Id, false);
+        assert!(!soviets.is_empty());
+        assert!(soviets.iter().all(|soldier| soldier.nation == b"ussr"));
+
+        let filter = RosterFilter {
+            role: Some(String::from("Sniper")),
+            ..RosterFilter::default()
+        };
+        assert_eq!(roster(&save.soldiers, &filter, SortKey::Id, false).len(), 1);
+
+        let ranks = Ranks::default();
+        let veteran = save
+            .soldiers
+            .iter()
+            .max_by_key(|soldier| soldier.xp)
+            .unwrap();
+        let rank = ranks.rank_for(veteran.xp).unwrap();
+        let filter = RosterFilter {
+            xp: Some(ranks.xp_range(rank)),
+            ..RosterFilter::default()
+        };
+        let same_rank = roster(&save.soldiers, &filter, SortKey::Id, false);
+        assert!(same_rank.iter().any(|soldier| soldier.id == veteran.id));
+        assert!(same_rank
+            .iter()
+            .all(|soldier| ranks.rank_for(soldier.xp) == Some(rank)));
+        assert!(same_rank.len() < save.soldiers.len());
+    }
+
+    #[test]
+    fn it_sorts_roster() {
+        let save = load_save("full_save.sav");
+        let filter = RosterFilter::default();
+
+        let by_age = roster(&save.soldiers, &filter, SortKey::Age, false);
+        assert_eq!(by_age[0].name, "Alston Davis");
+
+        let by_gender = roster(&save.soldiers, &filter, SortKey::Gender, false);
+        assert!(by_gender
+            .windows(2)
+            .all(|pair| pair[0].gender.to_byte() <= pair[1].gender.to_byte()));
+
+        let by_bravery = roster(
+            &save.soldiers,
+            &filter,
+            SortKey::Current(Stat::Bravery),
+            true,
+        );
+        assert!(by_bravery.windows(2).all(
+            |pair| pair[0].stats.current(Stat::Bravery) >= pair[1].stats.current(Stat::Bravery)
+        ));
+
+        // The bravest soldier now, but not when they were recruited
+        let mut save = save.clone();
+        let last = save.soldiers.last_mut().unwrap();
+        last.stats.set_current(Stat::Bravery, 100);
+        last.stats.set_original(Stat::Bravery, 1);
+        let last = last.id;
+        let by_bravery = roster(
+            &save.soldiers,
+            &filter,
+            SortKey::Current(Stat::Bravery),
+            true,
+        );
+        assert_eq!(by_bravery.first().unwrap().id, last);
+        let by_base_bravery = roster(&save.soldiers, &filter, SortKey::Base(Stat::Bravery), true);
+        assert!(by_base_bravery
+            .windows(2)
+            .all(|pair| pair[0].stats.original(Stat::Bravery)
+                >= pair[1].stats.original(Stat::Bravery)));
+        assert_eq!(by_base_bravery.last().unwrap().id, last);
+    }
+}
@@ -1,8 +1,46 @@
-use nom::{bytes::complete::take_until, multi::many0, IResult};
+use hex_literal::hex;
+use nom::{
+    bytes::complete::{tag, take_until},
+    combinator::{map_res, verify},
+    multi::{length_count, length_data, many0},
+    number::complete::le_u32,
+    sequence::{pair, preceded, tuple},
+    IResult,
+};
 
-use crate::soldier::{self, Soldier, SOLDIER_START};
+use std::collections::BTreeMap;
 
-#[derive(Debug)]
+use crate::soldier::{self, FieldSpans, Soldier, Span, SOLDIER_START};
+
+// o n e x
+const SAVE_MAGIC: &[u8] = hex!("6F 6E 65 78").as_slice();
+
+// Starts each section marker, followed by the length of the section's name and the name. A
+// section ends with the same name and a 2.
+const MARK: &[u8] = b"MARK";
+
+// M A R K 8 NULL NULL NULL U s e d M o d s
+const USED_MODS_START: &[u8] = hex!("4D 41 52 4B 08 00 00 00 55 73 65 64 4D 6F 64 73").as_slice();
+
+/// What the game's load screen shows about a save, from the start of the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveHeader {
+    pub version: u32,
+    pub name: String,
+    /// In game, e.g. `00:00 01 Sep 1979`
+    pub game_time: String,
+    /// In the real world, e.g. `23:50 06 Jul 2024`
+    pub saved_at: String,
+}
+
+impl SaveHeader {
+    /// The game names Iron Man saves itself, and players can't rename them.
+    pub fn is_iron_man(&self) -> bool {
+        self.name.starts_with("Iron Man (")
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Save {
     pub before_soldiers: Vec<u8>,
     pub soldiers: Vec<Soldier>,
@@ -23,58 +61,278 @@ impl Save {
     }
 
     pub fn get_soldier(&self, id: u32) -> Option<&Soldier> {
+        self.soldiers.iter().rev().find(|soldier| soldier.id == id)
+    }
+
+    /// One more than the highest ID in use, for soldiers added to the save.
+    pub fn next_soldier_id(&self) -> u32 {
         self.soldiers
             .iter()
-            .filter(|soldier| soldier.id == id as u32)
-            .last()
+            .map(|soldier| soldier.id)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    /// Adds a soldier exported from this save or another one under a new ID, in reserve rather
+    /// than on a dropship. Returns the new ID.
+    pub fn import_soldier(&mut self, mut soldier: Soldier) -> u32 {
+        soldier.id = self.next_soldier_id();
+        soldier.unassign();
+        self.soldiers.push(soldier);
+        self.soldiers.last().unwrap().id
     }
 
     pub fn get_soldier_mut(&mut self, id: u32) -> Option<&mut Soldier> {
         self.soldiers
             .iter_mut()
-            .filter(|soldier| soldier.id == id as u32)
-            .last()
+            .rev()
+            .find(|soldier| soldier.id == id)
+    }
+
+    pub fn header(&self) -> Option<SaveHeader> {
+        parse_save_header(&self.before_soldiers)
+            .ok()
+            .map(|(_, header)| header)
+    }
+
+    /// The mods that were enabled when the save was made.
+    pub fn used_mods(&self) -> Option<Vec<String>> {
+        parse_used_mods(&self.before_soldiers)
+            .ok()
+            .map(|(_, mods)| mods)
+    }
+}
+
+pub fn parse_save_header(input: &[u8]) -> IResult<&[u8], SaveHeader> {
+    let parse_string = |x: &[u8]| String::from_utf8(x.to_vec());
+    let (input, (version, name, game_time, saved_at)) = preceded(
+        tag(SAVE_MAGIC),
+        tuple((
+            le_u32,
+            map_res(length_data(le_u32), parse_string),
+            map_res(length_data(le_u32), parse_string),
+            map_res(length_data(le_u32), parse_string),
+        )),
+    )(input)?;
+    IResult::Ok((
+        input,
+        SaveHeader {
+            version,
+            name,
+            game_time,
+            saved_at,
+        },
+    ))
+}
+
+/// A soldier's field that was edited here and changed by the game too. The edit wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub soldier_id: u32,
+    /// As named in [`Soldier::record_parts`]
+    pub field: &'static str,
+}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "soldier {}: {}",
+            self.soldier_id,
+            self.field.to_lowercase()
+        )
+    }
+}
+
+/// Puts the edits made since `original` was loaded on top of a newer version of the same save,
+/// field by field, so the game's changes to everything that wasn't edited are kept. Recruits
+/// added here are added again, under a new ID if the game has hired someone under theirs.
+/// Everything else comes from the newer version, including which soldiers have left it.
+pub fn rebase_edits(original: &Save, edited: &Save, newer: &Save) -> (Save, Vec<Conflict>) {
+    let mut rebased = newer.clone();
+    let mut conflicts = vec![];
+    for soldier in &edited.soldiers {
+        match original.get_soldier(soldier.id) {
+            Some(before) => {
+                let Some(target) = rebased.get_soldier_mut(soldier.id) else {
+                    continue;
+                };
+                let (merged, fields) = rebase_soldier(before, soldier, target);
+                *target = merged;
+                conflicts.extend(fields.into_iter().map(|field| Conflict {
+                    soldier_id: soldier.id,
+                    field,
+                }));
+            }
+            None => {
+                let mut recruit = soldier.clone();
+                if rebased.get_soldier(recruit.id).is_some() {
+                    recruit.id = rebased.next_soldier_id();
+                }
+                rebased.soldiers.push(recruit);
+            }
+        }
+    }
+    (rebased, conflicts)
+}
+
+/// Puts the parts of a soldier's record edited since `original` on top of the newer version of
+/// them. Returns the merged soldier and the parts both versions changed differently.
+fn rebase_soldier(
+    original: &Soldier,
+    edited: &Soldier,
+    newer: &Soldier,
+) -> (Soldier, Vec<&'static str>) {
+    let mut conflicts = vec![];
+    let mut parts: Vec<_> = original
+        .record_parts()
+        .into_iter()
+        .zip(edited.record_parts())
+        .zip(newer.record_parts())
+        .map(|(((name, before), (_, edit)), (_, newer))| {
+            if before == edit || edit == newer {
+                return (name, newer);
+            }
+            if before != newer && !name.ends_with(" length") {
+                conflicts.push(name);
+            }
+            (name, edit)
+        })
+        .collect();
+    // Each length has to match the text after it, whichever version that came from
+    for i in 0..parts.len() - 1 {
+        if parts[i].0.ends_with(" length") {
+            parts[i].1 = (parts[i + 1].1.len() as u32).to_le_bytes().to_vec();
+        }
+    }
+    let record: Vec<u8> = parts.into_iter().flat_map(|(_, bytes)| bytes).collect();
+    let (_, soldier) =
+        soldier::parse_soldier(&record).expect("parts of parsed soldiers should parse together");
+    (soldier, conflicts)
+}
+
+pub fn parse_used_mods(input: &[u8]) -> IResult<&[u8], Vec<String>> {
+    let parse_string = |x: &[u8]| String::from_utf8(x.to_vec());
+    preceded(
+        pair(take_until(USED_MODS_START), tag(USED_MODS_START)),
+        length_count(le_u32, map_res(length_data(le_u32), parse_string)),
+    )(input)
+}
+
+/// Whether a save is all there: it ends where a section does, with every section it opened
+/// closed again. Saves the game is still writing stop partway through a section.
+pub fn is_complete(input: &[u8]) -> bool {
+    let mut open: Vec<&[u8]> = vec![];
+    let mut end = None;
+    for start in 0..input.len().saturating_sub(MARK.len()) {
+        // Anything else starting with MARK is just data
+        let Ok((rest, name)) = parse_section_marker(&input[start..]) else {
+            continue;
+        };
+        if name
+            .strip_suffix(b"2")
+            .is_some_and(|opened| open.last() == Some(&opened))
+        {
+            open.pop();
+        } else {
+            open.push(name);
+        }
+        end = Some(input.len() - rest.len());
+    }
+    open.is_empty() && end == Some(input.len())
+}
+
+fn parse_section_marker(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    preceded(
+        tag(MARK),
+        verify(length_data(le_u32), |name: &[u8]| {
+            !name.is_empty() && name.iter().all(u8::is_ascii_alphanumeric)
+        }),
+    )(input)
+}
+
+/// The game's newer version of a save, if the file now holds a whole save that isn't what was
+/// loaded or last written here.
+pub fn newer_version(on_disk: &[u8], bytes: &[u8]) -> Option<Save> {
+    if bytes == on_disk || !is_complete(bytes) {
+        return None;
+    }
+    parse_save(bytes).ok().map(|(_, save)| save)
+}
+
+/// Where each soldier's fields are in a save file, keyed by soldier ID and field name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanMap(BTreeMap<u32, FieldSpans>);
+
+impl SpanMap {
+    /// Where one of a soldier's fields is, by its name in [`Soldier::record_parts`].
+    pub fn get(&self, id: u32, field: &str) -> Option<Span> {
+        self.0
+            .get(&id)?
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, span)| span.clone())
     }
 }
 
 pub fn parse_save(input: &[u8]) -> IResult<&[u8], Save> {
+    let (unparsed, (save, _)) = parse_save_with_spans(input)?;
+    IResult::Ok((unparsed, save))
+}
+
+/// Parses a save, noting where each soldier's fields are in the file.
+pub fn parse_save_with_spans(input: &[u8]) -> IResult<&[u8], (Save, SpanMap)> {
     let (unparsed, before_soldiers) = take_until(SOLDIER_START)(input)?;
-    let (after_soldiers, soldiers) = many0(soldier::parse_soldier)(unparsed)?;
+    let (after_soldiers, soldiers) = many0(soldier::parse_soldier_with_spans)(unparsed)?;
+
+    let mut spans = SpanMap::default();
+    let mut offset = before_soldiers.len();
+    let soldiers = soldiers
+        .into_iter()
+        .map(|(soldier, soldier_spans)| {
+            let length = soldier_spans.last().map(|(_, span)| span.end).unwrap_or(0);
+            let soldier_spans = soldier_spans
+                .into_iter()
+                .map(|(name, span)| (name, span.start + offset..span.end + offset))
+                .collect();
+            // Like get_soldier, the last soldier with an ID is the one that counts
+            spans.0.insert(soldier.id, soldier_spans);
+            offset += length;
+            soldier
+        })
+        .collect();
+
     IResult::Ok((
         unparsed,
-        Save {
-            before_soldiers: before_soldiers.to_vec(),
-            soldiers,
-            after_soldiers: after_soldiers.to_vec(),
-        },
+        (
+            Save {
+                before_soldiers: before_soldiers.to_vec(),
+                soldiers,
+                after_soldiers: after_soldiers.to_vec(),
+            },
+            spans,
+        ),
     ))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs, path::PathBuf};
-
     use super::*;
+    use crate::testutil::{load_save, read_fixture};
 
     #[test]
     fn it_parses_just_soldier() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "single_soldier.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
-
-        let (_, save) = parse_save(&file).unwrap();
+        let save = load_save("single_soldier.sav");
         assert_eq!(save.before_soldiers.len(), 0);
         assert_eq!(save.soldiers.len(), 1);
         assert_eq!(save.after_soldiers.len(), 0);
+        assert_eq!(save.header(), None);
     }
 
     #[test]
     fn it_parses_just_soldier_round_trip() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "single_soldier.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
+        let file = read_fixture("single_soldier.sav");
 
         let (_, save) = parse_save(&file).unwrap();
         let output = save.serialise();
@@ -83,26 +341,160 @@ mod tests {
 
     #[test]
     fn it_parses_full_save() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "full_save.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
-
-        let (_, save) = parse_save(&file).unwrap();
+        let save = load_save("full_save.sav");
         assert_eq!(save.before_soldiers.len(), 2081);
         assert_eq!(save.soldiers.len(), 22);
         assert_eq!(save.after_soldiers.len(), 23740);
     }
 
+    #[test]
+    fn it_maps_soldier_fields() {
+        let file = read_fixture("full_save.sav");
+
+        let (_, (save, spans)) = parse_save_with_spans(&file).unwrap();
+        let first = save.soldiers.first().unwrap().id;
+        assert_eq!(spans.get(first, "Start marker"), Some(2081..2096));
+        for soldier in &save.soldiers {
+            let name = spans.get(soldier.id, "Name").unwrap();
+            assert_eq!(file[name], *soldier.name.as_bytes());
+            let id = spans.get(soldier.id, "ID").unwrap();
+            assert_eq!(file[id], soldier.id.to_le_bytes());
+            // Every byte of the record is accounted for, in the same order it's written
+            let fields = &spans.0[&soldier.id];
+            assert!(fields
+                .windows(2)
+                .all(|pair| pair[0].1.end == pair[1].1.start));
+            assert_eq!(
+                fields.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                soldier
+                    .record_parts()
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+            );
+        }
+        let last = save.soldiers.last().unwrap();
+        assert_eq!(
+            spans.get(last.id, "End marker").unwrap().end,
+            file.len() - save.after_soldiers.len()
+        );
+        assert_eq!(spans.get(first, "Not a field"), None);
+        assert_eq!(spans.get(1000, "Name"), None);
+    }
+
+    #[test]
+    fn it_ignores_saves_being_written() {
+        let file = read_fixture("full_save.sav");
+        let (_, original) = parse_save(&file).unwrap();
+        assert!(is_complete(&file));
+        assert!(is_complete(&read_fixture("single_soldier.sav")));
+        assert!(!is_complete(&read_fixture("random_data.sav")));
+
+        let mut newer = original.clone();
+        newer.soldiers[0].xp += 10;
+        let newer = newer.serialise();
+        assert!(newer_version(&file, &newer).is_some());
+        assert!(newer_version(&newer, &newer).is_none());
+        // Cut off partway through the soldiers and after them, which both still parse
+        for length in [file.len() / 10, file.len() / 2, file.len() - 1] {
+            assert!(parse_save(&newer[..length]).is_ok());
+            assert!(newer_version(&file, &newer[..length]).is_none());
+        }
+    }
+
+    #[test]
+    fn it_parses_save_header() {
+        let save = load_save("full_save.sav");
+        assert_eq!(
+            save.header(),
+            Some(SaveHeader {
+                version: 0x0102,
+                name: String::from("Iron Man (2024-07-06_20.46.00)"),
+                game_time: String::from("00:00 01 Sep 1979"),
+                saved_at: String::from("23:50 06 Jul 2024"),
+            })
+        );
+        assert!(save.header().unwrap().is_iron_man());
+
+        let mods = save.used_mods().unwrap();
+        assert_eq!(mods.len(), 18);
+        assert_eq!(mods[0], "xce");
+        assert_eq!(mods[17], "ambience forest");
+    }
+
     #[test]
     fn it_parses_full_save_round_trip() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "full_save.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
+        let file = read_fixture("full_save.sav");
 
         let (_, save) = parse_save(&file).unwrap();
         let output = save.serialise();
         assert_eq!(file, output);
     }
+
+    #[test]
+    fn it_imports_exported_soldier() {
+        let file = read_fixture("full_save.sav");
+        let (_, mut save) = parse_save(&file).unwrap();
+
+        // Soldier 16 is on a dropship
+        let export = save.get_soldier(16).unwrap().serialise();
+        let (_, soldier) = soldier::parse_soldier_export(&export).unwrap();
+        assert!(soldier::parse_soldier_export(&file).is_err());
+
+        let id = save.import_soldier(soldier);
+        assert_eq!(id, 26);
+        assert_eq!(save.soldiers.len(), 23);
+        let imported = save.get_soldier(id).unwrap();
+        assert_eq!(imported.name, save.get_soldier(16).unwrap().name);
+        assert!(imported.carrier.is_empty());
+    }
+
+    #[test]
+    fn it_rebases_edits_on_newer_save() {
+        let original = load_save("full_save.sav");
+
+        let mut edited = original.clone();
+        edited.soldiers[0].name = String::from("Edited Name");
+        edited.soldiers[1].xp += 5;
+        let mut recruit = edited.soldiers[1].clone();
+        recruit.id = 100;
+        edited.soldiers.push(recruit);
+        let mut collision = edited.soldiers[2].clone();
+        collision.id = 101;
+        edited.soldiers.push(collision);
+
+        let mut newer = original.clone();
+        newer.soldiers[0].xp += 10;
+        newer.soldiers[1].xp += 10;
+        newer.soldiers[2].xp += 10;
+        // The game has hired someone under the ID of one of the recruits added here
+        let mut hired = newer.soldiers[3].clone();
+        hired.id = 101;
+        newer.soldiers.push(hired);
+        newer.after_soldiers.push(0);
+
+        let (rebased, conflicts) = rebase_edits(&original, &edited, &newer);
+        assert_eq!(rebased.soldiers.len(), 25);
+        // Only the edited fields are taken, so what the game changed in the others stays
+        assert_eq!(rebased.soldiers[0].name, "Edited Name");
+        assert_eq!(rebased.soldiers[0].xp, newer.soldiers[0].xp);
+        assert_eq!(rebased.soldiers[2].xp, newer.soldiers[2].xp);
+        // Where both changed a field the edit wins, and is reported
+        assert_eq!(rebased.soldiers[1].xp, edited.soldiers[1].xp);
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                soldier_id: original.soldiers[1].id,
+                field: "XP"
+            }]
+        );
+        assert!(rebased.get_soldier(100).is_some());
+        assert_eq!(
+            rebased.get_soldier(101).unwrap().name,
+            newer.soldiers[3].name
+        );
+        assert_eq!(rebased.soldiers[24].id, 102);
+        assert_eq!(rebased.soldiers[24].name, edited.soldiers[2].name);
+        assert_eq!(rebased.after_soldiers, newer.after_soldiers);
+    }
 }
@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the game keeps its saves, relative to a Windows documents folder
+const SAVE_DIR: [&str; 3] = ["My Games", "Xenonauts", "save"];
+
+// Where Steam can be installed on Linux, relative to the home directory
+const STEAM_ROOTS: [&str; 3] = [
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+// Relative to a Steam library
+const LIBRARY_FOLDERS_FILE: &str = "steamapps/libraryfolders.vdf";
+const COMPAT_DATA_DIR: &str = "steamapps/compatdata";
+
+// Relative to a Proton prefix, which Steam makes for each game run through Proton
+const PROTON_DOCUMENTS_DIR: &str = "pfx/drive_c/users/steamuser/Documents";
+
+/// Looks for the game's save folders in the documents folder and in every Proton prefix in
+/// every Steam library. Only folders that exist are returned, each once.
+pub fn find_save_dirs(home: Option<&Path>, documents: Option<&Path>) -> Vec<PathBuf> {
+    let mut documents_dirs: Vec<PathBuf> = documents.into_iter().map(Path::to_path_buf).collect();
+    for library in home.map(steam_libraries).unwrap_or_default() {
+        let Ok(prefixes) = fs::read_dir(library.join(COMPAT_DATA_DIR)) else {
+            continue;
+        };
+        let mut prefixes: Vec<_> = prefixes.flatten().map(|prefix| prefix.path()).collect();
+        prefixes.sort();
+        documents_dirs.extend(
+            prefixes
+                .into_iter()
+                .map(|prefix| prefix.join(PROTON_DOCUMENTS_DIR)),
+        );
+    }
+
+    let mut save_dirs = vec![];
+    for documents_dir in documents_dirs {
+        let save_dir: PathBuf = SAVE_DIR
+            .iter()
+            .fold(documents_dir, |dir, part| dir.join(part));
+        // The same folder can be reached through symlinks, such as ~/.steam/steam
+        if let Ok(save_dir) = save_dir.canonicalize() {
+            if save_dir.is_dir() && !save_dirs.contains(&save_dir) {
+                save_dirs.push(save_dir);
+            }
+        }
+    }
+    save_dirs
+}
+
+/// Steam's own folders, followed by any other libraries they list.
+fn steam_libraries(home: &Path) -> Vec<PathBuf> {
+    let roots: Vec<_> = STEAM_ROOTS
+        .iter()
+        .filter_map(|root| home.join(root).canonicalize().ok())
+        .collect();
+    let mut libraries: Vec<PathBuf> = vec![];
+    for library in roots.iter().cloned().chain(roots.iter().flat_map(|root| {
+        fs::read_to_string(root.join(LIBRARY_FOLDERS_FILE))
+            .map(|vdf| parse_library_folders(&vdf))
+            .unwrap_or_default()
+    })) {
+        let library = library.canonicalize().unwrap_or(library);
+        if !libraries.contains(&library) {
+            libraries.push(library);
+        }
+    }
+    libraries
+}
+
+/// Picks the library paths out of Steam's `libraryfolders.vdf`, which has lines like
+/// `"path" "/mnt/games/SteamLibrary"` with tabs between the key and value.
+fn parse_library_folders(vdf: &str) -> Vec<PathBuf> {
+    vdf.lines()
+        .filter_map(|line| {
+            let mut parts = line.split('"').filter(|part| !part.trim().is_empty());
+            match (parts.next(), parts.next()) {
+                (Some("path"), Some(path)) => Some(PathBuf::from(path.replace("\\\\", "\\"))),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_library_folders() {
+        let vdf = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"/home/user/.local/share/Steam\"\n\t\t\"label\"\t\t\"\"\n\t}\n\t\"1\"\n\t{\n\t\t\"path\"\t\t\"/mnt/games/SteamLibrary\"\n\t}\n}\n";
+        assert_eq!(
+            parse_library_folders(vdf),
+            vec![
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/games/SteamLibrary"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_native_and_proton_save_dirs() {
+        let home = env::temp_dir().join(format!("xenonauts-save-dirs-{}", std::process::id()));
+        let save_dir = |documents: PathBuf| {
+            let dir = SAVE_DIR.iter().fold(documents, |dir, part| dir.join(part));
+            fs::create_dir_all(&dir).unwrap();
+            dir.canonicalize().unwrap()
+        };
+        let steam = home.join(".local/share/Steam");
+        let library = home.join("SteamLibrary");
+        let native = save_dir(home.join("Documents"));
+        let proton = save_dir(
+            steam
+                .join(COMPAT_DATA_DIR)
+                .join("223830")
+                .join(PROTON_DOCUMENTS_DIR),
+        );
+        let other_library = save_dir(
+            library
+                .join(COMPAT_DATA_DIR)
+                .join("1234")
+                .join(PROTON_DOCUMENTS_DIR),
+        );
+        // A prefix for some other game
+        fs::create_dir_all(
+            steam
+                .join(COMPAT_DATA_DIR)
+                .join("999")
+                .join(PROTON_DOCUMENTS_DIR),
+        )
+        .unwrap();
+        fs::write(
+            steam.join(LIBRARY_FOLDERS_FILE),
+            format!("\"path\"\t\t\"{}\"\n", library.display()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_save_dirs(Some(&home), Some(&home.join("Documents"))),
+            vec![native, proton, other_library]
+        );
+        assert!(find_save_dirs(Some(&home.join("missing")), None).is_empty());
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+}
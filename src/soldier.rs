@@ -1,23 +1,69 @@
 use hex_literal::hex;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
 use nom::{
     bytes::complete::{tag, take, take_until},
-    combinator::map_res,
-    multi::length_data,
-    number::complete::{float, le_f32, le_u16, le_u32, le_u8},
-    sequence::{delimited, tuple},
+    combinator::{all_consuming, map},
+    error::ErrorKind,
+    multi::{count, length_data},
+    number::complete::{le_f32, le_u32, le_u8},
+    sequence::tuple,
     IResult,
 };
 
 // M A R K 7 NULL NULL NULL S o l d i e r
 pub const SOLDIER_START: &[u8] = hex!("4D 41 52 4B 07 00 00 00 53 6F 6C 64 69 65 72").as_slice();
 
+// Stored as the dropship seat of soldiers who aren't on a dropship
+const NO_DROPSHIP_SEAT: u32 = u32::MAX;
+
+/// Where something is in a save, in bytes
+pub type Span = Range<usize>;
+
+/// Where each of a soldier's fields is, by name, in the order they're stored
+pub type FieldSpans = Vec<(&'static str, Span)>;
+
+/// Soldiers are exported on their own, stored exactly as they are in a save
+pub const SOLDIER_EXPORT_EXTENSION: &str = "soldier";
+
+// What follows the gender for every recruit the game offers for hire: a rifleman in basic
+// armour, with nothing else equipped
+const RECRUIT_REMAINING_BYTES: &[u8] = hex!(
+    "08 00 00 00 52 69 66 6C 65 6D 61 6E 00 00 00 00 00 16 00 00 00 44 65 61 64 53 6F 6C 64
+     69 65 72 2E 55 6E 6B 6E 6F 77 6E 4C 6F 63 0C 00 00 00 61 72 6D 6F 75 72 2E 62 61 73 69
+     63 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 0C 00 00 00 61 72 6D 6F 75 72 2E 62
+     61 73 69 63 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00"
+)
+.as_slice();
+
 // M A R K 8 NULL NULL NULL S o l d i e r 2
 const SOLDIER_END: &[u8] = hex!("4D 41 52 4B 08 00 00 00 53 6F 6C 64 69 65 72 32").as_slice();
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Gender {
-    Female = 0,
-    Male = 1,
+    Female,
+    Male,
+    /// A value only a mod knows the meaning of, kept as it is
+    Unknown(u8),
+}
+
+impl Gender {
+    pub fn from_byte(byte: u8) -> Self {
+        match byte {
+            0 => Gender::Female,
+            1 => Gender::Male,
+            byte => Gender::Unknown(byte),
+        }
+    }
+
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Gender::Female => 0,
+            Gender::Male => 1,
+            Gender::Unknown(byte) => byte,
+        }
+    }
 }
 
 impl std::fmt::Display for Gender {
@@ -26,14 +72,15 @@ impl std::fmt::Display for Gender {
             f,
             "{}",
             match self {
-                Gender::Female => "Female",
-                Gender::Male => "Male",
+                Gender::Female => String::from("Female"),
+                Gender::Male => String::from("Male"),
+                Gender::Unknown(byte) => format!("Unknown ({byte})"),
             }
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Soldier {
     pub id: u32,
     pub nationality: String,
@@ -43,210 +90,404 @@ pub struct Soldier {
     pub nation: Vec<u8>,
     pub stats: SoldierStats,
     pub xp: u32,
+    unknown_block: [u8; 36],
     pub age: f32,
     pub regiment: Vec<u8>,
     pub experience: Vec<u8>,
+    another_unknown_block: [u8; 4],
     pub carrier: Vec<u8>,
-    unknown_number: u32,
-    another_unknown_number: u32,
+    /// Where the soldier sits on their dropship, counting from 0, or nothing if they're not on
+    /// one
+    pub dropship_seat: Option<u32>,
+    // Between 41 and 61 as a number for soldiers who have been hired, so probably a stat the
+    // game doesn't show. It isn't a mission count, since soldiers back from the same single
+    // mission differ. Recruits waiting to be hired have 0 or leftover text here.
+    unknown_stat: [u8; 4],
     pub gender: Gender,
     remaining_bytes: Vec<u8>,
 }
 
 impl Soldier {
+    /// A blank recruit as the game offers them for hire, with the undecoded parts of their
+    /// record as they are for the game's own recruits. Everything that identifies them is left
+    /// empty to be filled in.
+    pub fn recruit(id: u32) -> Self {
+        Soldier {
+            id,
+            nationality: String::new(),
+            name: String::new(),
+            race: vec![],
+            face_number: 0,
+            nation: vec![],
+            stats: SoldierStats::new([0; 6]),
+            xp: 0,
+            unknown_block: [0; 36],
+            age: 0.0,
+            regiment: vec![],
+            experience: vec![],
+            another_unknown_block: [0; 4],
+            carrier: vec![],
+            dropship_seat: None,
+            unknown_stat: [0; 4],
+            gender: Gender::Male,
+            remaining_bytes: RECRUIT_REMAINING_BYTES.to_vec(),
+        }
+    }
+
     pub fn serialise(&self) -> Vec<u8> {
-        [
-            SOLDIER_START,
-            &self.id.to_le_bytes(),
-            &(self.nationality.len() as u32).to_le_bytes(),
-            &self.nationality.clone().into_bytes(),
-            &(self.name.len() as u32).to_le_bytes(),
-            &self.name.clone().into_bytes(),
-            &(self.race.len() as u32).to_le_bytes(),
-            &self.race,
-            &self.face_number.to_le_bytes(),
-            &(self.nation.len() as u32).to_le_bytes(),
-            &self.nation,
-            &self.stats.serialise(),
-            &self.xp.to_le_bytes(),
-            &[b'\0'; 36], // TODO replace with parsed data
-            &self.age.to_le_bytes(),
-            &(self.regiment.len() as u32).to_le_bytes(),
-            &self.regiment,
-            &(self.experience.len() as u32).to_le_bytes(),
-            &self.experience,
-            &[b'\0'; 4], // TODO replace with parsed data
-            &(self.carrier.len() as u32).to_le_bytes(),
-            &self.carrier,
-            &self.unknown_number.to_le_bytes(),
-            &self.another_unknown_number.to_le_bytes(),
-            &[self.gender as u8],
-            &self.remaining_bytes,
-            SOLDIER_END,
-        ]
-        .concat()
+        self.record_parts()
+            .into_iter()
+            .flat_map(|(_, bytes)| bytes)
+            .collect()
+    }
+
+    /// The soldier's record split into the fields it's made of, in the order they're stored.
+    pub fn record_parts(&self) -> Vec<(&'static str, Vec<u8>)> {
+        let length = |bytes: &[u8]| (bytes.len() as u32).to_le_bytes().to_vec();
+        let mut parts = vec![
+            ("Start marker", SOLDIER_START.to_vec()),
+            ("ID", self.id.to_le_bytes().to_vec()),
+            ("Nationality length", length(self.nationality.as_bytes())),
+            ("Nationality", self.nationality.clone().into_bytes()),
+            ("Name length", length(self.name.as_bytes())),
+            ("Name", self.name.clone().into_bytes()),
+            ("Race length", length(&self.race)),
+            ("Race", self.race.clone()),
+            ("Face number", self.face_number.to_le_bytes().to_vec()),
+            ("Flag length", length(&self.nation)),
+            ("Flag", self.nation.clone()),
+        ];
+        parts.extend(stat_names().zip(self.stats.serialise().chunks(4).map(<[u8]>::to_vec)));
+        parts.extend([
+            ("XP", self.xp.to_le_bytes().to_vec()),
+            ("Unknown block", self.unknown_block.to_vec()),
+            ("Age", self.age.to_le_bytes().to_vec()),
+            ("Regiment length", length(&self.regiment)),
+            ("Regiment", self.regiment.clone()),
+            ("Experience length", length(&self.experience)),
+            ("Experience", self.experience.clone()),
+            ("Another unknown block", self.another_unknown_block.to_vec()),
+            ("Dropship length", length(&self.carrier)),
+            ("Dropship", self.carrier.clone()),
+            (
+                "Dropship seat",
+                self.dropship_seat
+                    .unwrap_or(NO_DROPSHIP_SEAT)
+                    .to_le_bytes()
+                    .to_vec(),
+            ),
+            ("Unknown stat", self.unknown_stat.to_vec()),
+            ("Gender", vec![self.gender.to_byte()]),
+            ("Remaining bytes", self.remaining_bytes.clone()),
+            ("End marker", SOLDIER_END.to_vec()),
+        ]);
+        parts
+    }
+
+    /// The soldier's role, such as `Rifleman`, which the bytes after their gender start with.
+    pub fn role(&self) -> Option<String> {
+        let role: IResult<&[u8], &[u8]> = length_data(le_u32)(self.remaining_bytes.as_slice());
+        role.ok()
+            .and_then(|(_, role)| String::from_utf8(role.to_vec()).ok())
+    }
+
+    /// The undecoded stat after the dropship seat, as it is stored.
+    pub fn unknown_stat(&self) -> [u8; 4] {
+        self.unknown_stat
     }
+
+    /// Takes the soldier off their dropship, as if they had just been hired.
+    pub fn unassign(&mut self) {
+        self.carrier.clear();
+        self.dropship_seat = None;
+    }
+}
+
+/// Reads an exported soldier, which has to be the whole of the input.
+pub fn parse_soldier_export(input: &[u8]) -> IResult<&[u8], Soldier> {
+    all_consuming(parse_soldier)(input)
 }
 
 pub fn parse_soldier(input: &[u8]) -> IResult<&[u8], Soldier> {
-    let parse_string = |x: &[u8]| String::from_utf8(x.to_vec());
-    let parse_gender = |x: u8| match x {
-        0 => Ok(Gender::Female),
-        1 => Ok(Gender::Male),
-        _ => Err(()),
+    let (unparsed, (soldier, _)) = parse_soldier_with_spans(input)?;
+    IResult::Ok((unparsed, soldier))
+}
+
+/// Parses a soldier, noting where each field is relative to the start of their record. The
+/// fields are named the same as in [`Soldier::record_parts`].
+pub fn parse_soldier_with_spans(input: &[u8]) -> IResult<&[u8], (Soldier, FieldSpans)> {
+    let mut spans = Spans {
+        record: input,
+        spans: vec![],
     };
 
-    let (
+    let (i, _) = spans.field("Start marker", tag(SOLDIER_START), input)?;
+    let (i, id) = spans.field("ID", le_u32, i)?;
+    let (i, nationality) = spans.string("Nationality", "Nationality length", i)?;
+    let (i, name) = spans.string("Name", "Name length", i)?;
+    let (i, race) = spans.length_data("Race", "Race length", i)?;
+    let (i, face_number) = spans.field("Face number", le_u32, i)?;
+    let (i, nation) = spans.length_data("Flag", "Flag length", i)?;
+    let stats_start = i;
+    let (i, stats) = parse_soldier_stats(i)?;
+    for (n, name) in stat_names().enumerate() {
+        let start = spans.offset(stats_start) + n * 4;
+        spans.spans.push((name, start..start + 4));
+    }
+    let (i, xp) = spans.field("XP", le_u32, i)?;
+    // TODO figure out the unknown blocks
+    let (i, unknown_block) = spans.field("Unknown block", take(36u32), i)?;
+    let (i, age) = spans.field("Age", le_f32, i)?;
+    let (i, regiment) = spans.length_data("Regiment", "Regiment length", i)?;
+    let (i, experience) = spans.length_data("Experience", "Experience length", i)?;
+    let (i, another_unknown_block) = spans.field("Another unknown block", take(4u32), i)?;
+    let (i, carrier) = spans.length_data("Dropship", "Dropship length", i)?;
+    let (i, dropship_seat) = spans.field("Dropship seat", le_u32, i)?;
+    let (i, unknown_stat) = spans.field("Unknown stat", take(4u32), i)?;
+    let (i, gender) = spans.field("Gender", map(le_u8, Gender::from_byte), i)?;
+    let (i, remaining_bytes) = spans.field("Remaining bytes", take_until(SOLDIER_END), i)?;
+    let (unparsed, _) = spans.field("End marker", tag(SOLDIER_END), i)?;
+
+    IResult::Ok((
         unparsed,
         (
-            id,
-            nationality,
-            name,
-            race,
-            face_number,
-            nation,
-            stats,
-            xp,
-            _unknown, // TODO figure this out
-            age,
-            regiment,
-            experience,
-            _unknown_part_two, // TODO figure this out
-            carrier,
-            unknown_number,         // TODO figure this out
-            another_unknown_number, // TODO figure this out
-            gender,
-            remaining_bytes,
+            Soldier {
+                id,
+                nationality,
+                name,
+                race: race.to_vec(),
+                face_number,
+                nation: nation.to_vec(),
+                stats,
+                xp,
+                // Always the right length, since that's how much was taken
+                unknown_block: unknown_block.try_into().unwrap(),
+                age,
+                regiment: regiment.to_vec(),
+                experience: experience.to_vec(),
+                another_unknown_block: another_unknown_block.try_into().unwrap(),
+                carrier: carrier.to_vec(),
+                dropship_seat: Some(dropship_seat).filter(|seat| *seat != NO_DROPSHIP_SEAT),
+                unknown_stat: unknown_stat.try_into().unwrap(),
+                gender,
+                remaining_bytes: remaining_bytes.to_vec(),
+            },
+            spans.spans,
         ),
-    ) = delimited(
-        tag(SOLDIER_START),
-        tuple((
-            le_u32,
-            map_res(length_data(le_u32), parse_string),
-            map_res(length_data(le_u32), parse_string),
-            length_data(le_u32),
-            le_u32,
-            length_data(le_u32),
-            parse_soldier_stats,
-            le_u32,
-            take(36 as u32),
-            le_f32,
-            length_data(le_u32),
-            length_data(le_u32),
-            take(4 as u32),
-            length_data(le_u32),
-            le_u32,
-            le_u32,
-            map_res(le_u8, parse_gender),
-            take_until(SOLDIER_END),
-        )),
-        tag(SOLDIER_END),
-    )(input)?;
-    IResult::Ok((
-        unparsed,
-        Soldier {
-            id,
-            nationality,
-            name,
-            race: race.to_vec(),
-            face_number,
-            nation: nation.to_vec(),
-            stats,
-            xp,
-            age,
-            regiment: regiment.to_vec(),
-            experience: experience.to_vec(),
-            carrier: carrier.to_vec(),
-            unknown_number,
-            another_unknown_number,
-            gender,
-            remaining_bytes: remaining_bytes.to_vec(),
-        },
     ))
 }
 
-#[derive(Debug, Clone)]
+/// Where the fields of a record have been parsed from so far.
+struct Spans<'a> {
+    record: &'a [u8],
+    spans: FieldSpans,
+}
+
+impl<'a> Spans<'a> {
+    fn offset(&self, input: &[u8]) -> usize {
+        self.record.len() - input.len()
+    }
+
+    fn field<O>(
+        &mut self,
+        name: &'static str,
+        mut parser: impl FnMut(&'a [u8]) -> IResult<&'a [u8], O>,
+        input: &'a [u8],
+    ) -> IResult<&'a [u8], O> {
+        let (unparsed, output) = parser(input)?;
+        self.spans
+            .push((name, self.offset(input)..self.offset(unparsed)));
+        IResult::Ok((unparsed, output))
+    }
+
+    /// A length followed by that many bytes, each with their own span.
+    fn length_data(
+        &mut self,
+        name: &'static str,
+        length_name: &'static str,
+        input: &'a [u8],
+    ) -> IResult<&'a [u8], &'a [u8]> {
+        let (input, length) = self.field(length_name, le_u32, input)?;
+        self.field(name, take(length), input)
+    }
+
+    fn string(
+        &mut self,
+        name: &'static str,
+        length_name: &'static str,
+        input: &'a [u8],
+    ) -> IResult<&'a [u8], String> {
+        let (unparsed, bytes) = self.length_data(name, length_name, input)?;
+        let string = String::from_utf8(bytes.to_vec())
+            .map_err(|_| nom::Err::Error(nom::error::Error::new(input, ErrorKind::MapRes)))?;
+        IResult::Ok((unparsed, string))
+    }
+}
+
+/// One of a soldier's stats, each of which has a current and an original value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stat {
+    TimeUnits,
+    Health,
+    Strength,
+    Accuracy,
+    Reflexes,
+    Bravery,
+}
+
+impl Stat {
+    /// In the order they're stored
+    pub const ALL: [Stat; 6] = [
+        Stat::TimeUnits,
+        Stat::Health,
+        Stat::Strength,
+        Stat::Accuracy,
+        Stat::Reflexes,
+        Stat::Bravery,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stat::TimeUnits => "Time units",
+            Stat::Health => "Health",
+            Stat::Strength => "Strength",
+            Stat::Accuracy => "Accuracy",
+            Stat::Reflexes => "Reflexes",
+            Stat::Bravery => "Bravery",
+        }
+    }
+
+    /// Short enough for a table column
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Stat::TimeUnits => "TU",
+            Stat::Health => "HP",
+            Stat::Strength => "Str",
+            Stat::Accuracy => "Acc",
+            Stat::Reflexes => "Ref",
+            Stat::Bravery => "Bra",
+        }
+    }
+
+    // What the original value is called in a soldier's record
+    fn base_name(self) -> &'static str {
+        match self {
+            Stat::TimeUnits => "Time units base",
+            Stat::Health => "Health base",
+            Stat::Strength => "Strength base",
+            Stat::Accuracy => "Accuracy base",
+            Stat::Reflexes => "Reflexes base",
+            Stat::Bravery => "Bravery base",
+        }
+    }
+}
+
+impl std::fmt::Display for Stat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A soldier's stats, with their current values and the values they were recruited with. Both
+/// are indexed by [`Stat`], so a stat can't be mixed up with another.
+#[derive(Debug, Clone, PartialEq)]
 pub struct SoldierStats {
-    pub time_units_current: u32,
-    pub health_current: u32,
-    pub strength_current: u32,
-    pub accuracy_current: u32,
-    pub reflexes_current: u32,
-    pub bravery_current: u32,
-    pub time_units_original: u32,
-    pub health_original: u32,
-    pub strength_original: u32,
-    pub accuracy_original: u32,
-    pub reflexes_original: u32,
-    pub bravery_original: u32,
+    current: [u32; 6],
+    original: [u32; 6],
 }
 
 impl SoldierStats {
+    /// Stats as a new recruit has them, with nothing gained yet. The values are in the same
+    /// order as [`Stat::ALL`].
+    pub fn new(values: [u32; 6]) -> Self {
+        SoldierStats {
+            current: values,
+            original: values,
+        }
+    }
+
+    pub fn current(&self, stat: Stat) -> u32 {
+        self.current[stat as usize]
+    }
+
+    pub fn original(&self, stat: Stat) -> u32 {
+        self.original[stat as usize]
+    }
+
+    pub fn set_current(&mut self, stat: Stat, value: u32) {
+        self.current[stat as usize] = value;
+    }
+
+    pub fn set_original(&mut self, stat: Stat, value: u32) {
+        self.original[stat as usize] = value;
+    }
+
+    /// Each stat with its current and original values.
+    pub fn iter(&self) -> impl Iterator<Item = (Stat, u32, u32)> + '_ {
+        Stat::ALL
+            .into_iter()
+            .map(|stat| (stat, self.current(stat), self.original(stat)))
+    }
+
     fn serialise(&self) -> Vec<u8> {
-        [
-            self.time_units_current.to_le_bytes(),
-            self.health_current.to_le_bytes(),
-            self.strength_current.to_le_bytes(),
-            self.accuracy_current.to_le_bytes(),
-            self.reflexes_current.to_le_bytes(),
-            self.bravery_current.to_le_bytes(),
-            self.time_units_original.to_le_bytes(),
-            self.health_original.to_le_bytes(),
-            self.strength_original.to_le_bytes(),
-            self.accuracy_original.to_le_bytes(),
-            self.reflexes_original.to_le_bytes(),
-            self.bravery_original.to_le_bytes(),
-        ]
-        .concat()
+        self.current
+            .iter()
+            .chain(&self.original)
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+}
+
+/// How the value in a part of a soldier's record is stored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartKind {
+    Text,
+    U32,
+    F32,
+    U8,
+    /// Markers and anything else that isn't a single value
+    Bytes,
+}
+
+/// How one of the parts named in [`Soldier::record_parts`] is stored.
+pub fn part_kind(name: &str) -> PartKind {
+    match name {
+        "Nationality" | "Name" | "Race" | "Flag" | "Regiment" | "Experience" | "Dropship" => {
+            PartKind::Text
+        }
+        "ID" | "Face number" | "XP" | "Dropship seat" => PartKind::U32,
+        "Age" => PartKind::F32,
+        "Gender" => PartKind::U8,
+        _ if name.ends_with(" length") || stat_names().any(|stat| stat == name) => PartKind::U32,
+        _ => PartKind::Bytes,
     }
 }
 
+// The names of the stats' values in a soldier's record, in the order they're stored
+fn stat_names() -> impl Iterator<Item = &'static str> {
+    Stat::ALL
+        .map(Stat::name)
+        .into_iter()
+        .chain(Stat::ALL.map(Stat::base_name))
+}
+
 // fn parse_soldier_stats(input: &[u8]) -> IResult<&[u8], SoldierStats, VerboseError<&[u8]>> {
 fn parse_soldier_stats(input: &[u8]) -> IResult<&[u8], SoldierStats> {
-    let (
-        unparsed,
-        (
-            time_units_current,
-            health_current,
-            strength_current,
-            accuracy_current,
-            reflexes_current,
-            bravery_current,
-            time_units_original,
-            health_original,
-            strength_original,
-            accuracy_original,
-            reflexes_original,
-            bravery_original,
-        ),
-    ) = tuple((
-        le_u32, le_u32, le_u32, le_u32, le_u32, le_u32, le_u32, le_u32, le_u32, le_u32, le_u32,
-        le_u32,
-    ))(input)?;
+    let (unparsed, (current, original)) = tuple((count(le_u32, 6), count(le_u32, 6)))(input)?;
     IResult::Ok((
         unparsed,
         SoldierStats {
-            time_units_current,
-            health_current,
-            strength_current,
-            accuracy_current,
-            reflexes_current,
-            bravery_current,
-            time_units_original,
-            health_original,
-            strength_original,
-            accuracy_original,
-            reflexes_original,
-            bravery_original,
+            // Always the right length, since that's how many were parsed
+            current: current.try_into().unwrap(),
+            original: original.try_into().unwrap(),
         },
     ))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs, path::PathBuf};
-
     use super::*;
+    use crate::testutil::read_fixture;
 
     #[test]
     fn it_parses_stats() {
@@ -258,18 +499,18 @@ mod tests {
         .concat();
 
         let (_, stats) = parse_soldier_stats(&input).unwrap();
-        assert_eq!(stats.time_units_current, 54);
-        assert_eq!(stats.health_current, 55);
-        assert_eq!(stats.strength_current, 49);
-        assert_eq!(stats.accuracy_current, 67);
-        assert_eq!(stats.reflexes_current, 63);
-        assert_eq!(stats.bravery_current, 59);
-        assert_eq!(stats.time_units_original, 54);
-        assert_eq!(stats.health_original, 55);
-        assert_eq!(stats.strength_original, 49);
-        assert_eq!(stats.accuracy_original, 67);
-        assert_eq!(stats.reflexes_original, 63);
-        assert_eq!(stats.bravery_original, 59);
+        assert_eq!(stats.current(Stat::TimeUnits), 54);
+        assert_eq!(stats.current(Stat::Health), 55);
+        assert_eq!(stats.current(Stat::Strength), 49);
+        assert_eq!(stats.current(Stat::Accuracy), 67);
+        assert_eq!(stats.current(Stat::Reflexes), 63);
+        assert_eq!(stats.current(Stat::Bravery), 59);
+        assert_eq!(stats.original(Stat::TimeUnits), 54);
+        assert_eq!(stats.original(Stat::Health), 55);
+        assert_eq!(stats.original(Stat::Strength), 49);
+        assert_eq!(stats.original(Stat::Accuracy), 67);
+        assert_eq!(stats.original(Stat::Reflexes), 63);
+        assert_eq!(stats.original(Stat::Bravery), 59);
     }
 
     #[test]
@@ -286,12 +527,33 @@ mod tests {
         assert_eq!(input, output);
     }
 
+    #[test]
+    fn it_keeps_each_stat_in_its_own_place() {
+        let mut stats = SoldierStats::new([0; 6]);
+        for (n, stat) in Stat::ALL.into_iter().enumerate() {
+            stats.set_current(stat, 10 + n as u32);
+            stats.set_original(stat, 20 + n as u32);
+        }
+
+        let bytes = stats.serialise();
+        let (_, parsed) = parse_soldier_stats(&bytes).unwrap();
+        assert_eq!(parsed, stats);
+        for ((name, value), n) in stat_names().zip(bytes.chunks(4)).zip(0..) {
+            let stat = Stat::ALL[n % 6];
+            assert!(name.starts_with(stat.name()));
+            let expected = if n < 6 {
+                stats.current(stat)
+            } else {
+                stats.original(stat)
+            };
+            assert_eq!(value, expected.to_le_bytes());
+        }
+        assert_eq!(stats.iter().nth(4), Some((Stat::Reflexes, 14, 24)));
+    }
+
     #[test]
     fn it_parses_soldier() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "single_soldier.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
+        let file = read_fixture("single_soldier.sav");
 
         let (_, soldier) = parse_soldier(&file).unwrap();
         assert_eq!(soldier.id, 23);
@@ -304,31 +566,110 @@ mod tests {
         assert_eq!(soldier.regiment, b"regiment.japan1");
         assert_eq!(soldier.experience, b"experience.none");
         assert_eq!(soldier.carrier, b"Charlie - 1/13");
+        assert_eq!(soldier.dropship_seat, Some(1));
+        assert_eq!(soldier.unknown_stat, 57u32.to_le_bytes());
         assert_eq!(soldier.gender, Gender::Female);
 
-        assert_eq!(soldier.stats.time_units_current, 54);
-        assert_eq!(soldier.stats.health_current, 55);
-        assert_eq!(soldier.stats.strength_current, 49);
-        assert_eq!(soldier.stats.accuracy_current, 67);
-        assert_eq!(soldier.stats.reflexes_current, 63);
-        assert_eq!(soldier.stats.bravery_current, 59);
-        assert_eq!(soldier.stats.time_units_original, 54);
-        assert_eq!(soldier.stats.health_original, 55);
-        assert_eq!(soldier.stats.strength_original, 49);
-        assert_eq!(soldier.stats.accuracy_original, 67);
-        assert_eq!(soldier.stats.reflexes_original, 63);
-        assert_eq!(soldier.stats.bravery_original, 59);
+        assert_eq!(soldier.stats.current(Stat::TimeUnits), 54);
+        assert_eq!(soldier.stats.current(Stat::Health), 55);
+        assert_eq!(soldier.stats.current(Stat::Strength), 49);
+        assert_eq!(soldier.stats.current(Stat::Accuracy), 67);
+        assert_eq!(soldier.stats.current(Stat::Reflexes), 63);
+        assert_eq!(soldier.stats.current(Stat::Bravery), 59);
+        assert_eq!(soldier.stats.original(Stat::TimeUnits), 54);
+        assert_eq!(soldier.stats.original(Stat::Health), 55);
+        assert_eq!(soldier.stats.original(Stat::Strength), 49);
+        assert_eq!(soldier.stats.original(Stat::Accuracy), 67);
+        assert_eq!(soldier.stats.original(Stat::Reflexes), 63);
+        assert_eq!(soldier.stats.original(Stat::Bravery), 59);
     }
 
     #[test]
     fn it_parses_soldier_round_trip() {
-        let filepath: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "single_soldier.sav"]
-            .iter()
-            .collect();
-        let file = fs::read(filepath).unwrap();
+        let file = read_fixture("single_soldier.sav");
 
         let (_, soldier) = parse_soldier(&file).unwrap();
         let output = soldier.serialise();
         assert_eq!(file, output);
     }
+
+    #[test]
+    fn it_seats_each_soldier_on_their_dropship() {
+        let file = read_fixture("full_save.sav");
+        let (_, save) = crate::save::parse_save(&file).unwrap();
+
+        let mut seats: Vec<_> = save
+            .soldiers
+            .iter()
+            .filter(|soldier| !soldier.carrier.is_empty())
+            .map(|soldier| soldier.dropship_seat.unwrap())
+            .collect();
+        seats.sort();
+        assert_eq!(seats, (0..8).collect::<Vec<_>>());
+        assert!(save
+            .soldiers
+            .iter()
+            .filter(|soldier| soldier.carrier.is_empty())
+            .all(|soldier| soldier.dropship_seat.is_none()));
+        assert_eq!(save.serialise(), file);
+
+        let mut soldier = save.soldiers.last().unwrap().clone();
+        soldier.unassign();
+        let (_, unassigned) = parse_soldier(&soldier.serialise()).unwrap();
+        assert_eq!(unassigned.dropship_seat, None);
+    }
+
+    #[test]
+    fn it_keeps_unknown_genders() {
+        let mut file = read_fixture("single_soldier.sav");
+
+        let (_, (_, spans)) = parse_soldier_with_spans(&file).unwrap();
+        let (_, gender) = spans.iter().find(|(name, _)| *name == "Gender").unwrap();
+        file[gender.start] = 7;
+        let (_, soldier) = parse_soldier(&file).unwrap();
+        assert_eq!(soldier.gender, Gender::Unknown(7));
+        assert_eq!(soldier.gender.to_string(), "Unknown (7)");
+        assert_eq!(soldier.serialise(), file);
+        assert_eq!(Gender::from_byte(1), Gender::Male);
+    }
+
+    #[test]
+    fn it_knows_how_each_part_is_stored() {
+        let (_, soldier) = parse_soldier(&read_fixture("single_soldier.sav")).unwrap();
+        for (name, bytes) in soldier.record_parts() {
+            match part_kind(name) {
+                PartKind::U32 | PartKind::F32 => assert_eq!(bytes.len(), 4, "{}", name),
+                PartKind::U8 => assert_eq!(bytes.len(), 1, "{}", name),
+                PartKind::Text => assert!(String::from_utf8(bytes).is_ok(), "{}", name),
+                PartKind::Bytes => {}
+            }
+        }
+        assert_eq!(part_kind("Flag"), PartKind::Text);
+        assert_eq!(part_kind("Bravery base"), PartKind::U32);
+    }
+
+    #[test]
+    fn it_keeps_unknown_blocks() {
+        let mut file = read_fixture("single_soldier.sav");
+
+        let (_, soldier) = parse_soldier(&file).unwrap();
+        let parts = soldier.record_parts();
+        let offset = |name: &str| -> usize {
+            parts
+                .iter()
+                .take_while(|(part, _)| *part != name)
+                .map(|(_, bytes)| bytes.len())
+                .sum()
+        };
+        let (_, unknown_block) = parts
+            .iter()
+            .find(|(part, _)| *part == "Unknown block")
+            .unwrap();
+        assert_eq!(unknown_block.len(), 36);
+        file[offset("Unknown block") + 8] = 3;
+        file[offset("Another unknown block")] = 1;
+
+        let (_, soldier) = parse_soldier(&file).unwrap();
+        assert_eq!(soldier.serialise(), file);
+    }
 }
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+// Relative to the game's install directory
+const STRINGS_DIR: &str = "assets/strings";
+
+/// Display names for the keys stored in a save, such as `regiment.japan1`.
+#[derive(Debug, Clone, Default)]
+pub struct StringTable {
+    strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    /// The display name for a key, or the key itself if the game doesn't have one.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map(String::as_str).unwrap_or(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.strings.contains_key(key)
+    }
+}
+
+/// Loads every string table in the game's strings folder. A missing folder gives an empty table,
+/// since not every install (or mod) ships one.
+pub fn load_string_tables(install_dir: &Path) -> Result<StringTable, Box<dyn Error>> {
+    let dir = install_dir.join(STRINGS_DIR);
+    if !dir.is_dir() {
+        return Result::Ok(StringTable::default());
+    }
+
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+        .collect();
+    // Later files win, so load in a stable order
+    paths.sort();
+
+    let mut strings = HashMap::new();
+    for path in paths {
+        strings.extend(parse_string_table(&fs::read_to_string(path)?)?);
+    }
+    Result::Ok(StringTable { strings })
+}
+
+/// Parses a string table, either as `<string key="...">value</string>` elements or as a
+/// spreadsheet whose rows have the key in the first cell and the value in the second.
+pub fn parse_string_table(xml: &str) -> Result<HashMap<String, String>, roxmltree::Error> {
+    let document = Document::parse(xml)?;
+    let mut strings = HashMap::new();
+    for node in document.descendants() {
+        if node.has_tag_name("string") {
+            if let (Some(key), Some(value)) = (node.attribute("key"), node.text()) {
+                strings.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        } else if node.tag_name().name() == "Row" {
+            let cells: Vec<String> = node
+                .children()
+                .filter(|cell| cell.tag_name().name() == "Cell")
+                .map(cell_text)
+                .collect();
+            if let [key, value, ..] = cells.as_slice() {
+                if !key.is_empty() && !value.is_empty() {
+                    strings.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    Result::Ok(strings)
+}
+
+fn cell_text(cell: Node) -> String {
+    cell.descendants()
+        .filter(|node| node.is_text())
+        .filter_map(|node| node.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_string_elements() {
+        let strings = parse_string_table(
+            r#"<strings>
+                <string key="regiment.japan1">1st Airborne Brigade</string>
+                <string key="experience.none"> None </string>
+            </strings>"#,
+        )
+        .unwrap();
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings["regiment.japan1"], "1st Airborne Brigade");
+        assert_eq!(strings["experience.none"], "None");
+    }
+
+    #[test]
+    fn it_parses_spreadsheet_rows() {
+        let strings = parse_string_table(
+            r#"<Workbook xmlns="urn:schemas-microsoft-com:office:spreadsheet"
+                        xmlns:ss="urn:schemas-microsoft-com:office:spreadsheet">
+                <Worksheet ss:Name="Strings">
+                    <Table>
+                        <Row>
+                            <Cell><Data ss:Type="String">regiment.japan1</Data></Cell>
+                            <Cell><Data ss:Type="String">1st Airborne Brigade</Data></Cell>
+                        </Row>
+                        <Row>
+                            <Cell><Data ss:Type="String">japan</Data></Cell>
+                        </Row>
+                    </Table>
+                </Worksheet>
+            </Workbook>"#,
+        )
+        .unwrap();
+        assert_eq!(strings.len(), 1);
+
+        let table = StringTable { strings };
+        assert_eq!(table.get("regiment.japan1"), "1st Airborne Brigade");
+        assert_eq!(table.get("regiment.japan2"), "regiment.japan2");
+    }
+}
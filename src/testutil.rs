@@ -0,0 +1,19 @@
+use std::{fs, path::PathBuf};
+
+use crate::save::{parse_save, Save};
+
+/// The folder the test saves are kept in
+pub fn fixtures_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests"].iter().collect()
+}
+
+/// The bytes of one of the test saves, such as `full_save.sav`.
+pub fn read_fixture(name: &str) -> Vec<u8> {
+    fs::read(fixtures_dir().join(name)).unwrap()
+}
+
+/// One of the test saves, parsed.
+pub fn load_save(name: &str) -> Save {
+    let (_, save) = parse_save(&read_fixture(name)).unwrap();
+    save
+}
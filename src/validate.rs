@@ -0,0 +1,416 @@
+use std::collections::{BTreeSet, HashMap};
+use std::ops::RangeInclusive;
+
+use crate::save::Save;
+use crate::soldier::{Gender, Soldier, Stat};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Severity::Warning => "Warning",
+                Severity::Error => "Error",
+            }
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    Id,
+    Name,
+    Nationality,
+    Race,
+    FaceNumber,
+    Nation,
+    Stat(Stat),
+    Xp,
+    Age,
+    Regiment,
+    Experience,
+    Gender,
+    DropshipSeat,
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Field::Id => "ID",
+                Field::Name => "Name",
+                Field::Nationality => "Nationality",
+                Field::Race => "Race",
+                Field::FaceNumber => "Face",
+                Field::Nation => "Flag",
+                Field::Stat(stat) => stat.name(),
+                Field::Xp => "XP",
+                Field::Age => "Age",
+                Field::Regiment => "Regiment",
+                Field::Experience => "Experience",
+                Field::Gender => "Gender",
+                Field::DropshipSeat => "Seat",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Issue {
+    pub soldier_id: u32,
+    pub field: Field,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Limits a soldier is checked against.
+///
+/// The lookup tables are filled in from the game's own data when it has been loaded; without
+/// it nations, regiments and experiences only get a format check, and faces are only checked
+/// for races and genders that portraits were found for.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    pub stat_range: RangeInclusive<u32>,
+    pub age_range: RangeInclusive<f32>,
+    pub max_name_length: usize,
+    pub max_key_length: usize,
+    pub nations: Option<BTreeSet<String>>,
+    pub regiments: Option<BTreeSet<String>>,
+    pub experiences: Option<BTreeSet<String>>,
+    /// Number of portraits available for each race and gender.
+    pub face_counts: HashMap<(String, Gender), u32>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            stat_range: 1..=100,
+            age_range: 18.0..=65.0,
+            max_name_length: 32,
+            max_key_length: 64,
+            nations: None,
+            regiments: None,
+            experiences: None,
+            face_counts: HashMap::new(),
+        }
+    }
+}
+
+pub fn validate_with(save: &Save, rules: &Rules) -> Vec<Issue> {
+    let mut id_counts: HashMap<u32, usize> = HashMap::new();
+    for soldier in &save.soldiers {
+        *id_counts.entry(soldier.id).or_default() += 1;
+    }
+
+    let mut seat_counts: HashMap<(&[u8], u32), usize> = HashMap::new();
+    for soldier in &save.soldiers {
+        if let Some(seat) = soldier.dropship_seat {
+            *seat_counts
+                .entry((soldier.carrier.as_slice(), seat))
+                .or_default() += 1;
+        }
+    }
+
+    let mut issues = vec![];
+    for soldier in &save.soldiers {
+        if soldier
+            .dropship_seat
+            .is_some_and(|seat| seat_counts[&(soldier.carrier.as_slice(), seat)] > 1)
+        {
+            issues.push(Issue {
+                soldier_id: soldier.id,
+                field: Field::DropshipSeat,
+                severity: Severity::Error,
+                message: format!(
+                    "Seat {} on {} is taken by more than one soldier",
+                    soldier.dropship_seat.unwrap_or_default(),
+                    String::from_utf8_lossy(&soldier.carrier)
+                ),
+            });
+        }
+        if id_counts[&soldier.id] > 1 {
+            issues.push(Issue {
+                soldier_id: soldier.id,
+                field: Field::Id,
+                severity: Severity::Error,
+                message: format!("ID {} is used by more than one soldier", soldier.id),
+            });
+        }
+        issues.extend(validate_soldier(soldier, rules));
+    }
+    issues
+}
+
+pub fn validate_soldier(soldier: &Soldier, rules: &Rules) -> Vec<Issue> {
+    let mut issues = vec![];
+    let mut report = |field: Field, severity: Severity, message: String| {
+        issues.push(Issue {
+            soldier_id: soldier.id,
+            field,
+            severity,
+            message,
+        })
+    };
+
+    if soldier.name.trim().is_empty() {
+        report(Field::Name, Severity::Error, "Name is empty".to_string());
+    } else if soldier.name.chars().count() > rules.max_name_length {
+        report(
+            Field::Name,
+            Severity::Warning,
+            format!("Name is longer than {} characters", rules.max_name_length),
+        );
+    }
+
+    if soldier.nationality.trim().is_empty() {
+        report(
+            Field::Nationality,
+            Severity::Warning,
+            "Nationality is empty".to_string(),
+        );
+    } else if soldier.nationality.chars().count() > rules.max_name_length {
+        report(
+            Field::Nationality,
+            Severity::Warning,
+            format!(
+                "Nationality is longer than {} characters",
+                rules.max_name_length
+            ),
+        );
+    }
+
+    for (stat, current, original) in soldier.stats.iter() {
+        let field = Field::Stat(stat);
+        if current < original {
+            report(
+                field,
+                Severity::Error,
+                format!("{field} {current} is below its base value {original}"),
+            );
+        }
+        for value in [current, original] {
+            if !rules.stat_range.contains(&value) {
+                report(
+                    field,
+                    Severity::Warning,
+                    format!(
+                        "{field} {value} is outside {}-{}",
+                        rules.stat_range.start(),
+                        rules.stat_range.end()
+                    ),
+                );
+            }
+        }
+    }
+
+    if !soldier.age.is_finite() || soldier.age < 0.0 {
+        report(
+            Field::Age,
+            Severity::Error,
+            format!("Age {} is not a valid age", soldier.age),
+        );
+    } else if !rules.age_range.contains(&soldier.age) {
+        report(
+            Field::Age,
+            Severity::Warning,
+            format!(
+                "Age {} is outside {}-{}",
+                soldier.age,
+                rules.age_range.start(),
+                rules.age_range.end()
+            ),
+        );
+    }
+
+    let race = String::from_utf8_lossy(&soldier.race);
+    if race.is_empty() {
+        report(Field::Race, Severity::Error, "Race is empty".to_string());
+    } else if let Some(count) = rules.face_counts.get(&(race.to_string(), soldier.gender)) {
+        if soldier.face_number >= *count {
+            report(
+                Field::FaceNumber,
+                Severity::Warning,
+                format!(
+                    "Face {} does not exist for {} {} soldiers ({} available)",
+                    soldier.face_number,
+                    soldier.gender.to_string().to_lowercase(),
+                    race,
+                    count
+                ),
+            );
+        }
+    }
+
+    let nation = String::from_utf8_lossy(&soldier.nation);
+    if nation.is_empty() {
+        report(Field::Nation, Severity::Error, "Flag is empty".to_string());
+    } else if let Some(nations) = &rules.nations {
+        if !nations.contains(nation.as_ref()) {
+            report(
+                Field::Nation,
+                Severity::Warning,
+                format!("Unknown nation {nation}"),
+            );
+        }
+    } else if !nation
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        report(
+            Field::Nation,
+            Severity::Warning,
+            format!("Flag {nation} does not look like a nation key"),
+        );
+    }
+
+    let regiment = String::from_utf8_lossy(&soldier.regiment);
+    if let Some(regiments) = &rules.regiments {
+        if !regiments.contains(regiment.as_ref()) {
+            report(
+                Field::Regiment,
+                Severity::Warning,
+                format!("Unknown regiment {regiment}"),
+            );
+        }
+    } else if !regiment.starts_with("regiment.") {
+        report(
+            Field::Regiment,
+            Severity::Warning,
+            format!("Regiment {regiment} does not start with \"regiment.\""),
+        );
+    }
+
+    // The game itself writes "None" for soldiers recruited without a background
+    let experience = String::from_utf8_lossy(&soldier.experience);
+    if let Some(experiences) = &rules.experiences {
+        if experience != "None" && !experiences.contains(experience.as_ref()) {
+            report(
+                Field::Experience,
+                Severity::Warning,
+                format!("Unknown experience {experience}"),
+            );
+        }
+    } else if experience != "None" && !experience.starts_with("experience.") {
+        report(
+            Field::Experience,
+            Severity::Warning,
+            format!("Experience {experience} does not start with \"experience.\""),
+        );
+    }
+
+    for (field, value) in [
+        (Field::Race, &race),
+        (Field::Nation, &nation),
+        (Field::Regiment, &regiment),
+        (Field::Experience, &experience),
+    ] {
+        if value.len() > rules.max_key_length {
+            report(
+                field,
+                Severity::Warning,
+                format!("{field} is longer than {} bytes", rules.max_key_length),
+            );
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::load_save;
+
+    #[test]
+    fn it_accepts_valid_soldier() {
+        let save = load_save("single_soldier.sav");
+        assert_eq!(validate_with(&save, &Rules::default()), vec![]);
+    }
+
+    #[test]
+    fn it_flags_full_save() {
+        let save = load_save("full_save.sav");
+        let issues = validate_with(&save, &Rules::default());
+
+        // Alston Davis has been edited to be 8 years old
+        assert!(issues
+            .iter()
+            .any(|issue| issue.soldier_id == 24 && issue.field == Field::Age));
+        assert!(issues
+            .iter()
+            .all(|issue| issue.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn it_flags_broken_soldier() {
+        let mut save = load_save("single_soldier.sav");
+        let soldier = &mut save.soldiers[0];
+        soldier.name = String::from("  ");
+        soldier.stats.set_current(Stat::Bravery, 10);
+        soldier.regiment = b"japan1".to_vec();
+        soldier.face_number = 20;
+
+        let rules = Rules {
+            face_counts: HashMap::from([((String::from("asi"), Gender::Female), 8)]),
+            ..Rules::default()
+        };
+        let fields: Vec<_> = validate_with(&save, &rules)
+            .into_iter()
+            .map(|issue| (issue.field, issue.severity))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Field::Name, Severity::Error),
+                (Field::Stat(Stat::Bravery), Severity::Error),
+                (Field::FaceNumber, Severity::Warning),
+                (Field::Regiment, Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_flags_duplicate_ids() {
+        let mut save = load_save("full_save.sav");
+        save.soldiers[1].id = save.soldiers[0].id;
+        let duplicates = validate_with(&save, &Rules::default())
+            .into_iter()
+            .filter(|issue| issue.field == Field::Id)
+            .count();
+        assert_eq!(duplicates, 2);
+    }
+
+    #[test]
+    fn it_flags_shared_dropship_seats() {
+        let mut save = load_save("full_save.sav");
+        let seated: Vec<_> = save
+            .soldiers
+            .iter()
+            .filter(|soldier| soldier.dropship_seat.is_some())
+            .map(|soldier| soldier.id)
+            .collect();
+        let seat = save.get_soldier(seated[0]).unwrap().dropship_seat;
+        save.get_soldier_mut(seated[1]).unwrap().dropship_seat = seat;
+        let shared: Vec<_> = validate_with(&save, &Rules::default())
+            .into_iter()
+            .filter(|issue| issue.field == Field::DropshipSeat)
+            .map(|issue| issue.soldier_id)
+            .collect();
+        assert_eq!(shared, seated[..2]);
+
+        // The same seat on another dropship is a different seat
+        save.get_soldier_mut(seated[1]).unwrap().carrier = b"Alpha - 1/13".to_vec();
+        assert!(validate_with(&save, &Rules::default())
+            .iter()
+            .all(|issue| issue.field != Field::DropshipSeat));
+    }
+}
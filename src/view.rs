@@ -1,45 +1,300 @@
-use std::error::Error;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::io::Read;
+use std::ops::RangeInclusive;
 use std::option::Option::{None, Some};
 use std::path::PathBuf;
 use std::result::Result::{Err, Ok};
-use std::u16::MAX;
+use std::time::{Duration, Instant};
 
 use iced::alignment::{Horizontal, Vertical};
-use iced::theme::Button;
+use iced::event::{self, Event};
+use iced::keyboard::{self, key::Named, Key};
+use iced::theme::{self, Button};
+use iced::widget::scrollable::{Direction, Properties};
 use iced::widget::{
-    button, column, horizontal_space, keyed_column, pick_list, row, scrollable, slider, text,
-    text_input,
+    button, canvas, checkbox, column, container, focus_next, focus_previous, horizontal_space,
+    image, keyed_column, pick_list, row, scrollable, slider, text, text_input, tooltip,
+};
+use iced::{
+    executor, window, Alignment, Application, Color, Command, Element, Font, Length, Settings,
+    Size, Subscription, Theme,
 };
-use iced::{Alignment, Element, Length, Sandbox, Settings};
 use iced_aw::{number_input, BOOTSTRAP_FONT};
-use rfd::{FileDialog, MessageDialog, MessageLevel};
+use rfd::{AsyncFileDialog, AsyncMessageDialog, MessageButtons, MessageDialogResult, MessageLevel};
 
-use crate::save::{self, Save};
-use crate::soldier::{Gender, Soldier, SoldierStats};
+use crate::browser::{self, BrowserSortKey, SaveSummary};
+use crate::charts::{self, Histogram, RadarChart};
+use crate::compare;
+use crate::config::{self, Config};
+use crate::dashboard;
+use crate::diff;
+use crate::gamedata::{self, GameData};
+use crate::generator::{self, RecruitPool};
+use crate::inspect;
+use crate::names;
+use crate::portraits::{self, Portraits};
+use crate::rank::{self, Rank, Ranks};
+use crate::roster::{self, RosterFilter, SortKey};
+use crate::save::{self, Save, SaveHeader, SpanMap};
+use crate::savedirs;
+use crate::soldier::{self, Gender, Soldier, SoldierStats, Stat, SOLDIER_EXPORT_EXTENSION};
+use crate::strings::StringTable;
+use crate::validate::{self, Field, Issue, Severity};
+use crate::watch;
 
 pub fn run() -> iced::Result {
-    let mut settings: Settings<()> = Settings::default();
+    // A config that can't be read is replaced with the defaults the next time it's saved
+    let config = config::config_path()
+        .and_then(|path| config::load_config(&path).ok())
+        .unwrap_or_default();
+    let mut settings = Settings::with_flags(config.clone());
     settings.fonts.push(std::borrow::Cow::Owned(
         iced_aw::BOOTSTRAP_FONT_BYTES.to_vec(),
     ));
+    if let Some((width, height)) = config.window_size {
+        settings.window.size = Size::new(width, height);
+    }
+    // Closing is handled by the editor, so the settings can be saved first
+    settings.window.exit_on_close_request = false;
     Editor::run(settings)
 }
 
-enum Editor {
+struct Editor {
+    /// Kept between runs, and written out when something in it changes
+    config: Config,
+    /// The headers of the recent saves, for those that could be read
+    recent_headers: HashMap<PathBuf, SaveHeader>,
+    /// The game's save folders found on this machine, native ones first
+    save_dirs: Vec<PathBuf>,
+    game_dir: Option<PathBuf>,
+    mod_dirs: Vec<PathBuf>,
+    game_data: GameData,
+    /// Kept when opening another save
+    roster_filter: RosterFilter,
+    sort_key: SortKey,
+    sort_descending: bool,
+    view_mode: ViewMode,
+    show_shortcuts: bool,
+    /// What's happening in the background, or the result of the last thing that did
+    status: Option<String>,
+    /// Shown instead of the editor while open
+    browser: Option<SaveBrowser>,
+    session: Session,
+}
+
+/// How soon a second click on a save has to come to open it
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// Every save in the save folders, read one at a time in the background.
+struct SaveBrowser {
+    saves: Vec<SaveSummary>,
+    /// Saves still being read
+    pending: Vec<PathBuf>,
+    /// Files that couldn't be read as saves
+    unreadable: usize,
+    search: String,
+    sort_key: BrowserSortKey,
+    sort_descending: bool,
+    selected: Option<PathBuf>,
+    /// The last save clicked and when, to tell double clicks apart
+    last_click: Option<(PathBuf, Instant)>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ViewMode {
+    /// One soldier at a time, with every field
+    Form,
+    /// Every soldier at once, one per row
+    Table,
+    /// The ticked soldiers' stats side by side
+    Compare,
+    /// How the stats of the soldiers in the roster are spread
+    Charts,
+    /// A summary of the whole roster
+    Dashboard,
+    /// The selected soldier's record as raw bytes
+    Inspect,
+    /// What changed since an earlier save
+    Diff,
+}
+
+enum Session {
     NoData,
     Save {
         path: PathBuf,
-        save: Save,
+        save: Box<Save>,
         selected_soldier_id: u32,
+        issues: Vec<Issue>,
+        highlighted_field: Option<Field>,
+        /// Fields being typed in by hand instead of picked from the game data
+        custom_fields: HashSet<Field>,
+        show_face_gallery: bool,
+        /// Soldiers ticked in the roster for bulk actions
+        marked_soldier_ids: BTreeSet<u32>,
+        /// The file as last loaded or written, to tell the editor's own writes apart from the
+        /// game's
+        on_disk: Vec<u8>,
+        /// Where each soldier's fields are in `on_disk`
+        spans: SpanMap,
+        /// A version the game wrote after the save was opened, until it's reloaded or ignored
+        newer_on_disk: Option<Box<Save>>,
+        /// An earlier save to show the changes since
+        diff_base: Option<Box<(PathBuf, Save)>>,
     },
 }
 
+/// An entry in a dropdown of game data keys, with an escape hatch for values the game data
+/// doesn't know about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Choice {
+    Key { key: String, name: String },
+    Other,
+}
+
+impl std::fmt::Display for Choice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Choice::Key { name, .. } => write!(f, "{}", name),
+            Choice::Other => write!(f, "Other…"),
+        }
+    }
+}
+
+/// An entry in a roster filter dropdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterChoice<T> {
+    Any,
+    Only(T),
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for FilterChoice<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterChoice::Any => write!(f, "Any"),
+            FilterChoice::Only(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl<T> From<FilterChoice<T>> for Option<T> {
+    fn from(choice: FilterChoice<T>) -> Self {
+        match choice {
+            FilterChoice::Any => None,
+            FilterChoice::Only(value) => Some(value),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for FilterChoice<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => FilterChoice::Any,
+            Some(value) => FilterChoice::Only(value),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Message {
     OpenFile,
     SaveFile,
-    SelectSoldier { id: u32 },
+    OpenGameFolder,
+    AddModFolder,
+    ToggleFaceGallery,
+    AddRecruit,
+    RerollSoldier,
+    MarkSoldier {
+        id: u32,
+        marked: bool,
+    },
+    UpdateSearch(String),
+    GenderFilterSelected(FilterChoice<Gender>),
+    NationFilterSelected(FilterChoice<String>),
+    RoleFilterSelected(FilterChoice<String>),
+    RankFilterSelected(FilterChoice<String>),
+    SortKeySelected(SortKey),
+    ToggleSortDirection,
+    SortByColumn(SortKey),
+    ViewModeSelected(ViewMode),
+    /// Opens a soldier in the form from elsewhere, such as the dashboard
+    OpenSoldier {
+        id: u32,
+    },
+    /// Shows the soldiers the filter lets through in the form
+    ShowFiltered(RosterFilter),
+    SelectNextSoldier,
+    SelectPreviousSoldier,
+    FocusNext,
+    FocusPrevious,
+    /// Picks the stat that +/- nudge
+    FocusStat(Stat),
+    NudgeStat(i32),
+    ToggleShortcuts,
+    CloseShortcuts,
+    /// Copies a stat, or all of them, from one ticked soldier to the other ticked ones
+    CopyStats {
+        from: u32,
+        stat: Option<Stat>,
+    },
+    SaveFilePicked(Option<PathBuf>),
+    PickDiffSave,
+    DiffSavePicked(Option<PathBuf>),
+    DiffSaveLoaded(PathBuf, Result<Save, String>),
+    /// Opens a save, once any unsaved changes to the current one have been dealt with
+    LoadSave(PathBuf),
+    FileDropped(PathBuf),
+    ExportSoldier,
+    SoldierExportPicked(u32, Option<PathBuf>),
+    SoldierExported(Result<(), String>),
+    SoldierFileRead(Result<Vec<u8>, String>),
+    RecentHeadersRead(HashMap<PathBuf, SaveHeader>),
+    SaveFoldersFound(Vec<PathBuf>),
+    OpenSaveFolder,
+    SaveFolderPicked(Option<PathBuf>),
+    ToggleBrowser,
+    SavesListed(Vec<PathBuf>),
+    SaveSummarised(PathBuf, Result<SaveSummary, String>),
+    UpdateBrowserSearch(String),
+    BrowserSortKeySelected(BrowserSortKey),
+    ToggleBrowserSortDirection,
+    BrowserSaveClicked(PathBuf),
+    WindowResized(Size),
+    CloseRequested(window::Id),
+    SaveLoaded(PathBuf, Result<Save, String>),
+    /// With what was written, once it's on disk
+    SaveWritten(Result<Vec<u8>, String>),
+    /// Saving over a version the game wrote since the save was opened
+    OverwriteSave,
+    SaveChangedOnDisk,
+    DiskSaveRead(Result<Vec<u8>, String>),
+    /// Swaps in the game's newer version, with the fields edited here put back on top if asked
+    ReloadSave {
+        keep_edits: bool,
+    },
+    /// Keeps the save as it is here, to overwrite the game's version on the next save
+    IgnoreNewerSave,
+    GameFolderPicked(Option<PathBuf>),
+    GameDataLoaded(PathBuf, Result<GameData, String>),
+    ModFolderPicked(Option<PathBuf>),
+    NamesFilePicked(Option<PathBuf>),
+    NamesFileLoaded(Result<String, String>),
+    DialogClosed,
+    /// An edit made on a soldier's table row rather than in the form
+    EditSoldier {
+        id: u32,
+        edit: Box<Message>,
+    },
+    RenameSoldier,
+    RenameMarked,
+    ImportNames,
+    SelectSoldier {
+        id: u32,
+    },
+    JumpToIssue {
+        id: u32,
+        field: Field,
+    },
     UpdateName(String),
     UpdateNationality(String),
     UpdateRace(String),
@@ -47,81 +302,793 @@ enum Message {
     UpdateExperience(String),
     UpdateFlag(String),
     GenderSelected(Gender),
+    RankSelected(Rank),
+    ChoiceSelected(Field, Choice),
     UpdateAge(f32),
     UpdateXP(u32),
-    UpdateTimeUnits(u32),
-    UpdateTimeUnitsBase(u32),
-    UpdateHealth(u32),
-    UpdateHealthBase(u32),
-    UpdateStrength(u32),
-    UpdateStrengthBase(u32),
-    UpdateAccuracy(u32),
-    UpdateAccuracyBase(u32),
-    UpdateReflexes(u32),
-    UpdateReflexesBase(u32),
-    UpdateBravery(u32),
-    UpdateBraveryBase(u32),
+    /// Sets a stat's current value, which can't go below its base value
+    UpdateStat(Stat, u32),
+    UpdateStatBase(Stat, u32),
     UpdateFaceNumber(u32),
+    UpdateDropshipSeat(u32),
 }
 
-impl Sandbox for Editor {
+impl Editor {
+    /// The game folder followed by each mod folder, in the order they override each other
+    fn data_roots(&self) -> Vec<PathBuf> {
+        self.game_dir
+            .iter()
+            .chain(self.mod_dirs.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Where to open saves from: the folder picked in the settings, else wherever the last save
+    /// was, else the first save folder found.
+    fn save_dialog_directory(&self) -> Option<PathBuf> {
+        self.config
+            .save_dir
+            .clone()
+            .or_else(|| self.config.last_directory.clone())
+            .or_else(|| self.save_dirs.first().cloned())
+    }
+
+    fn has_unsaved_changes(&self) -> bool {
+        match &self.session {
+            Session::Save { save, on_disk, .. } => save.serialise() != *on_disk,
+            Session::NoData => false,
+        }
+    }
+
+    /// Writes out the settings kept between runs. Failing to is only worth a mention, since
+    /// the editor works just as well without them.
+    fn save_config(&mut self) {
+        self.config.game_dir.clone_from(&self.game_dir);
+        self.config.mod_dirs.clone_from(&self.mod_dirs);
+        self.config.sort_key = self.sort_key;
+        self.config.sort_descending = self.sort_descending;
+        if let Some(path) = config::config_path() {
+            if let Err(e) = config::save_config(&path, &self.config) {
+                self.status = Some(format!("Could not save settings: {}", e));
+            }
+        }
+    }
+
+    /// Rescans the data mods can add to or override
+    fn reload_mod_data(&mut self) -> Command<Message> {
+        let roots = self.data_roots();
+        let mut errors = vec![];
+        self.game_data.portraits = portraits::scan_portraits(&roots);
+        match rank::load_ranks(&roots) {
+            Ok(ranks) => self.game_data.ranks = ranks.unwrap_or_default(),
+            Err(e) => errors.push(error_dialog("Could not load rank thresholds!", e)),
+        }
+        match names::load_name_lists(&roots) {
+            Ok(names) => self.game_data.names = names,
+            Err(e) => errors.push(error_dialog("Could not load name lists!", e)),
+        }
+        Command::batch(errors)
+    }
+}
+
+impl Application for Editor {
+    type Executor = executor::Default;
     type Message = Message;
+    type Theme = Theme;
+    type Flags = Config;
 
-    fn new() -> Self {
-        Editor::NoData
+    fn new(config: Config) -> (Self, Command<Message>) {
+        let mut editor = Editor {
+            game_dir: None,
+            mod_dirs: config.mod_dirs.clone(),
+            game_data: GameData::default(),
+            roster_filter: RosterFilter::default(),
+            sort_key: config.sort_key,
+            sort_descending: config.sort_descending,
+            view_mode: ViewMode::Form,
+            show_shortcuts: false,
+            status: None,
+            browser: None,
+            session: Session::NoData,
+            recent_headers: HashMap::new(),
+            save_dirs: vec![],
+            config,
+        };
+        let mut commands = vec![
+            Command::perform(
+                read_save_headers(editor.config.recent_saves.clone()),
+                Message::RecentHeadersRead,
+            ),
+            Command::perform(find_save_dirs(), Message::SaveFoldersFound),
+        ];
+        // The game folder is only remembered once its data loads, so it's loaded again here
+        match editor.config.game_dir.clone() {
+            Some(dir) => {
+                editor.status = Some(String::from("Loading game data…"));
+                commands.push(Command::perform(
+                    load_game_data(dir.clone()),
+                    move |result| Message::GameDataLoaded(dir, result),
+                ));
+            }
+            None if !editor.mod_dirs.is_empty() => commands.push(editor.reload_mod_data()),
+            None => {}
+        }
+        (editor, Command::batch(commands))
     }
 
     fn title(&self) -> String {
         String::from("Xenonauts Soldier Editor")
     }
 
-    fn update(&mut self, message: Message) {
-        if let Message::OpenFile = message {
-            let path = FileDialog::new()
-                .add_filter("Save file", &["sav"])
-                .pick_file();
-
-            if let Some(path) = path {
-                let save_or_error = load_save(&path);
-                *self = match save_or_error {
-                    Ok(save) => {
-                        let selected_soldier_id =
-                            save.soldiers.get(0).map(|soldier| soldier.id).unwrap_or(0);
-                        Editor::Save {
-                            path,
-                            save,
-                            selected_soldier_id,
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::FocusNext => return focus_next(),
+            Message::FocusPrevious => return focus_previous(),
+            Message::ToggleShortcuts => self.show_shortcuts = !self.show_shortcuts,
+            Message::CloseShortcuts => self.show_shortcuts = false,
+            Message::RecentHeadersRead(headers) => {
+                self.recent_headers = headers;
+                return Command::none();
+            }
+            Message::SaveFoldersFound(save_dirs) => {
+                self.save_dirs = save_dirs;
+                return Command::none();
+            }
+            Message::OpenSaveFolder => {
+                return Command::perform(
+                    pick_folder(
+                        "Select the folder to open saves from",
+                        self.save_dialog_directory(),
+                    ),
+                    Message::SaveFolderPicked,
+                );
+            }
+            Message::SaveFolderPicked(Some(dir)) => {
+                self.config.save_dir = Some(dir);
+                self.save_config();
+                return Command::none();
+            }
+            Message::ToggleBrowser => {
+                if self.browser.take().is_some() {
+                    return Command::none();
+                }
+                self.browser = Some(SaveBrowser {
+                    saves: vec![],
+                    pending: vec![],
+                    unreadable: 0,
+                    search: String::new(),
+                    sort_key: BrowserSortKey::default(),
+                    sort_descending: true,
+                    selected: None,
+                    last_click: None,
+                });
+                let dirs = self
+                    .config
+                    .save_dir
+                    .iter()
+                    .chain(self.save_dirs.iter())
+                    .cloned()
+                    .collect();
+                return Command::perform(list_saves(dirs), Message::SavesListed);
+            }
+            Message::SavesListed(paths) => {
+                let Some(browser) = &mut self.browser else {
+                    return Command::none();
+                };
+                browser.pending.clone_from(&paths);
+                return Command::batch(paths.into_iter().map(|path| {
+                    Command::perform(summarise_save(path.clone()), move |result| {
+                        Message::SaveSummarised(path, result)
+                    })
+                }));
+            }
+            Message::SaveSummarised(path, result) => {
+                // Saves read for a browser that has since been closed are dropped
+                if let Some(browser) = &mut self.browser {
+                    if let Some(i) = browser.pending.iter().position(|pending| *pending == path) {
+                        browser.pending.remove(i);
+                        match result {
+                            Ok(summary) => browser.saves.push(summary),
+                            Err(_) => browser.unreadable += 1,
                         }
                     }
-                    Err(e) => {
-                        MessageDialog::new()
-                            .set_level(MessageLevel::Error)
-                            .set_title("Could not open save file!")
-                            .set_description(format!("{:#?}", e))
-                            .show();
-                        Editor::NoData
+                }
+                return Command::none();
+            }
+            Message::UpdateBrowserSearch(search) => {
+                if let Some(browser) = &mut self.browser {
+                    browser.search = search;
+                }
+                return Command::none();
+            }
+            Message::BrowserSortKeySelected(key) => {
+                if let Some(browser) = &mut self.browser {
+                    browser.sort_key = key;
+                }
+                return Command::none();
+            }
+            Message::ToggleBrowserSortDirection => {
+                if let Some(browser) = &mut self.browser {
+                    browser.sort_descending = !browser.sort_descending;
+                }
+                return Command::none();
+            }
+            Message::BrowserSaveClicked(path) => {
+                let Some(browser) = &mut self.browser else {
+                    return Command::none();
+                };
+                let now = Instant::now();
+                let double_click = browser.last_click.as_ref().is_some_and(|(last, at)| {
+                    *last == path && now.duration_since(*at) <= DOUBLE_CLICK_TIME
+                });
+                browser.selected = Some(path.clone());
+                browser.last_click = Some((path.clone(), now));
+                if double_click {
+                    return self.update(Message::SaveFilePicked(Some(path)));
+                }
+                return Command::none();
+            }
+            Message::WindowResized(size) => {
+                self.config.window_size = Some((size.width, size.height));
+                return Command::none();
+            }
+            Message::CloseRequested(id) => {
+                self.save_config();
+                return window::close(id);
+            }
+            _ => {}
+        }
+
+        // Nudges become ordinary edits of the highlighted stat, so they follow the same rules
+        let message = match (message, &self.session) {
+            (
+                Message::NudgeStat(delta),
+                Session::Save {
+                    save,
+                    selected_soldier_id,
+                    highlighted_field: Some(Field::Stat(stat)),
+                    ..
+                },
+            ) => match save.get_soldier(*selected_soldier_id) {
+                Some(soldier) => {
+                    let range = self.game_data.rules().stat_range;
+                    let value = soldier.stats.current(*stat).saturating_add_signed(delta);
+                    Message::UpdateStat(*stat, value.clamp(*range.start(), *range.end()))
+                }
+                None => return Command::none(),
+            },
+            (Message::NudgeStat(_), _) => return Command::none(),
+            (message, _) => message,
+        };
+
+        match message {
+            Message::OpenGameFolder => {
+                return Command::perform(
+                    pick_folder(
+                        "Select the Xenonauts install folder",
+                        self.config.last_directory.clone(),
+                    ),
+                    Message::GameFolderPicked,
+                );
+            }
+            Message::GameFolderPicked(Some(dir)) => {
+                self.status = Some(String::from("Loading game data…"));
+                return Command::perform(load_game_data(dir.clone()), move |result| {
+                    Message::GameDataLoaded(dir, result)
+                });
+            }
+            Message::GameDataLoaded(dir, result) => {
+                self.status = None;
+                return match result {
+                    Ok(game_data) => {
+                        self.game_data = game_data;
+                        self.game_dir = Some(dir);
+                        self.save_config();
+                        self.reload_mod_data()
                     }
+                    Err(e) => error_dialog("Could not load game data!", e),
                 };
             }
+            Message::AddModFolder => {
+                return Command::perform(
+                    pick_folder("Select a mod folder", self.config.last_directory.clone()),
+                    Message::ModFolderPicked,
+                );
+            }
+            Message::ModFolderPicked(Some(dir)) => {
+                self.mod_dirs.push(dir);
+                self.save_config();
+                return self.reload_mod_data();
+            }
+            Message::OpenFile => {
+                return Command::perform(
+                    pick_file(
+                        "Select a save file",
+                        "Save file",
+                        &["sav"],
+                        self.save_dialog_directory(),
+                    ),
+                    Message::SaveFilePicked,
+                );
+            }
+            Message::SaveFilePicked(Some(path)) if self.has_unsaved_changes() => {
+                return Command::perform(
+                    AsyncMessageDialog::new()
+                        .set_level(MessageLevel::Warning)
+                        .set_title("Discard unsaved changes?")
+                        .set_description(format!(
+                            "Opening {} will lose the changes made to this save since it was \
+                             last saved.",
+                            path.display()
+                        ))
+                        .set_buttons(MessageButtons::YesNo)
+                        .show(),
+                    move |result| match result {
+                        MessageDialogResult::Yes => Message::LoadSave(path),
+                        _ => Message::DialogClosed,
+                    },
+                );
+            }
+            Message::SaveFilePicked(Some(path)) => return self.update(Message::LoadSave(path)),
+            Message::PickDiffSave => {
+                return Command::perform(
+                    pick_file(
+                        "Select an earlier save to compare with",
+                        "Save file",
+                        &[browser::SAVE_EXTENSION],
+                        self.save_dialog_directory(),
+                    ),
+                    Message::DiffSavePicked,
+                );
+            }
+            Message::DiffSavePicked(Some(path)) => {
+                return Command::perform(load_save(path.clone()), move |result| {
+                    Message::DiffSaveLoaded(path, result)
+                });
+            }
+            Message::LoadSave(path) => {
+                self.status = Some(format!("Loading {}…", path.display()));
+                return Command::perform(load_save(path.clone()), move |result| {
+                    Message::SaveLoaded(path, result)
+                });
+            }
+            Message::FileDropped(path) => {
+                let extension = path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or("");
+                if extension.eq_ignore_ascii_case(browser::SAVE_EXTENSION) {
+                    return self.update(Message::SaveFilePicked(Some(path)));
+                }
+                if !extension.eq_ignore_ascii_case(SOLDIER_EXPORT_EXTENSION) {
+                    return error_dialog(
+                        "Could not open dropped file!",
+                        format!("{} is not a save or an exported soldier", path.display()),
+                    );
+                }
+                return match self.session {
+                    Session::Save { .. } => {
+                        Command::perform(read_file(path), Message::SoldierFileRead)
+                    }
+                    Session::NoData => error_dialog(
+                        "Could not import soldier!",
+                        "Open the save to import the soldier into first",
+                    ),
+                };
+            }
+            _ => {}
         }
 
-        if let Editor::Save {
+        if let Message::SaveLoaded(path, result) = message {
+            self.status = None;
+            return match result {
+                Ok(save) => {
+                    let selected_soldier_id =
+                        save.soldiers.first().map(|soldier| soldier.id).unwrap_or(0);
+                    let issues = validate::validate_with(&save, &self.game_data.rules());
+                    match save.header() {
+                        Some(header) => self.recent_headers.insert(path.clone(), header),
+                        None => self.recent_headers.remove(&path),
+                    };
+                    self.config.add_recent_save(path.clone());
+                    self.save_config();
+                    self.browser = None;
+                    self.view_mode = ViewMode::Dashboard;
+                    self.session = Session::Save {
+                        path,
+                        selected_soldier_id,
+                        issues,
+                        highlighted_field: None,
+                        custom_fields: HashSet::new(),
+                        show_face_gallery: false,
+                        marked_soldier_ids: BTreeSet::new(),
+                        spans: file_spans(&save.serialise()),
+                        on_disk: save.serialise(),
+                        newer_on_disk: None,
+                        diff_base: None,
+                        save: Box::new(save),
+                    };
+                    Command::none()
+                }
+                Err(e) => {
+                    self.session = Session::NoData;
+                    error_dialog("Could not open save file!", e)
+                }
+            };
+        }
+
+        if let Message::ViewModeSelected(view_mode) = message {
+            self.view_mode = view_mode;
+        }
+        if let Message::OpenSoldier { .. } | Message::ShowFiltered(_) = message {
+            self.view_mode = ViewMode::Form;
+        }
+
+        if let Session::Save {
             path,
             save,
             selected_soldier_id,
-        } = self
+            issues,
+            highlighted_field,
+            custom_fields,
+            show_face_gallery,
+            marked_soldier_ids,
+            on_disk,
+            spans,
+            newer_on_disk,
+            diff_base,
+        } = &mut self.session
         {
-            if let Message::SaveFile = message {
-                if let Err(e) = fs::write(path, save.serialise()) {
-                    MessageDialog::new()
-                        .set_level(MessageLevel::Error)
-                        .set_title("Could not write updated save file!")
-                        .set_description(format!("{:#?}", e))
-                        .show();
+            // Table edits apply to the soldier on that row, which becomes the selected one
+            let message = match message {
+                Message::EditSoldier { id, edit } => {
+                    if id != *selected_soldier_id {
+                        *selected_soldier_id = id;
+                        *highlighted_field = None;
+                        custom_fields.clear();
+                    }
+                    *edit
                 }
+                message => message,
+            };
+            if let (Message::SaveFile, Some(_)) = (&message, &newer_on_disk) {
+                return Command::perform(
+                    AsyncMessageDialog::new()
+                        .set_level(MessageLevel::Warning)
+                        .set_title("Overwrite the game's newer save?")
+                        .set_description(
+                            "The game has written this save since it was opened here. Saving \
+                             now will lose what the game changed.",
+                        )
+                        .set_buttons(MessageButtons::YesNo)
+                        .show(),
+                    |result| match result {
+                        MessageDialogResult::Yes => Message::OverwriteSave,
+                        _ => Message::DialogClosed,
+                    },
+                );
             }
-            if let Message::SelectSoldier { id } = message {
-                *selected_soldier_id = id as u32;
+            if let Message::SaveFile | Message::OverwriteSave = message {
+                self.status = Some(String::from("Saving…"));
+                let bytes = save.serialise();
+                return Command::perform(write_save(path.clone(), bytes.clone()), |result| {
+                    Message::SaveWritten(result.map(|()| bytes))
+                });
+            }
+            if let Message::ExportSoldier = message {
+                let Some(soldier) = save.get_soldier(*selected_soldier_id) else {
+                    return Command::none();
+                };
+                let id = soldier.id;
+                return Command::perform(
+                    pick_save_location(
+                        "Export soldier",
+                        "Exported soldier",
+                        &[SOLDIER_EXPORT_EXTENSION],
+                        format!("{}.{}", soldier.name, SOLDIER_EXPORT_EXTENSION),
+                        self.config.last_directory.clone(),
+                    ),
+                    move |path| Message::SoldierExportPicked(id, path),
+                );
+            }
+            if let Message::SoldierExportPicked(id, Some(path)) = &message {
+                let Some(soldier) = save.get_soldier(*id) else {
+                    return Command::none();
+                };
+                return Command::perform(
+                    write_save(path.clone(), soldier.serialise()),
+                    Message::SoldierExported,
+                );
+            }
+            if let Message::SoldierExported(result) = message {
+                return match result {
+                    Ok(()) => {
+                        self.status = Some(String::from("Exported"));
+                        Command::none()
+                    }
+                    Err(e) => error_dialog("Could not export soldier!", e),
+                };
+            }
+            if let Message::SoldierFileRead(result) = &message {
+                let soldier = result.clone().and_then(|bytes| {
+                    soldier::parse_soldier_export(&bytes)
+                        .map(|(_, soldier)| soldier)
+                        .map_err(|err| err.to_owned().to_string())
+                });
+                match soldier {
+                    Ok(soldier) => {
+                        self.status = Some(format!("Imported {}", soldier.name));
+                        *selected_soldier_id = save.import_soldier(soldier);
+                        *highlighted_field = None;
+                        custom_fields.clear();
+                    }
+                    Err(e) => return error_dialog("Could not import soldier!", e),
+                }
+            }
+            if let Message::SaveChangedOnDisk = message {
+                return Command::perform(read_file(path.clone()), Message::DiskSaveRead);
+            }
+            if let Message::DiskSaveRead(result) = message {
+                // The editor's own writes are ignored, as are saves the game is halfway through
+                // writing, since another change will follow
+                if let Some(newer) = result
+                    .ok()
+                    .and_then(|bytes| save::newer_version(on_disk, &bytes))
+                {
+                    *newer_on_disk = Some(Box::new(newer));
+                }
+                return Command::none();
+            }
+            if let Message::IgnoreNewerSave = message {
+                if let Some(newer) = newer_on_disk.take() {
+                    *on_disk = newer.serialise();
+                    *spans = file_spans(on_disk);
+                }
+                return Command::none();
+            }
+            if let Message::ReloadSave { keep_edits } = message {
+                if let Some(newer) = newer_on_disk.take() {
+                    let (reloaded, conflicts) = match save::parse_save(on_disk) {
+                        Ok((_, original)) if keep_edits => {
+                            save::rebase_edits(&original, save, &newer)
+                        }
+                        _ => ((*newer).clone(), vec![]),
+                    };
+                    *on_disk = newer.serialise();
+                    *spans = file_spans(on_disk);
+                    **save = reloaded;
+                    marked_soldier_ids.retain(|id| save.get_soldier(*id).is_some());
+                    if save.get_soldier(*selected_soldier_id).is_none() {
+                        *selected_soldier_id =
+                            save.soldiers.first().map(|soldier| soldier.id).unwrap_or(0);
+                        *highlighted_field = None;
+                        custom_fields.clear();
+                    }
+                    self.status = Some(String::from(if keep_edits {
+                        "Reloaded, keeping your edits"
+                    } else {
+                        "Reloaded"
+                    }));
+                    if !conflicts.is_empty() {
+                        return Command::perform(
+                            AsyncMessageDialog::new()
+                                .set_level(MessageLevel::Warning)
+                                .set_title("The game changed some of the same fields")
+                                .set_description(format!(
+                                    "Your edits were kept over what the game wrote for:\n{}",
+                                    conflicts
+                                        .iter()
+                                        .map(|conflict| conflict.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join("\n")
+                                ))
+                                .show(),
+                            |_| Message::DialogClosed,
+                        );
+                    }
+                }
+            }
+            if let Message::DiffSaveLoaded(path, result) = message {
+                return match result {
+                    Ok(earlier) => {
+                        *diff_base = Some(Box::new((path, earlier)));
+                        Command::none()
+                    }
+                    Err(e) => error_dialog("Could not open save to compare with!", e),
+                };
+            }
+            if let Message::SaveWritten(result) = message {
+                self.status = None;
+                return match result {
+                    Ok(bytes) => {
+                        // Whatever the game had written is now overwritten
+                        *spans = file_spans(&bytes);
+                        *on_disk = bytes;
+                        *newer_on_disk = None;
+                        self.status = Some(String::from("Saved"));
+                        Command::none()
+                    }
+                    Err(e) => error_dialog("Could not write updated save file!", e),
+                };
+            }
+            if let Message::SelectSoldier { id } | Message::OpenSoldier { id } = message {
+                *selected_soldier_id = id;
+                *highlighted_field = None;
+                custom_fields.clear();
+            }
+            if let Message::FocusStat(stat) = message {
+                *highlighted_field = Some(Field::Stat(stat));
+            }
+            if let Message::SelectNextSoldier | Message::SelectPreviousSoldier = message {
+                let ids: Vec<_> = roster::roster(
+                    &save.soldiers,
+                    &self.roster_filter,
+                    self.sort_key,
+                    self.sort_descending,
+                )
+                .iter()
+                .map(|soldier| soldier.id)
+                .collect();
+                let next = match ids.iter().position(|id| id == selected_soldier_id) {
+                    Some(i) if matches!(message, Message::SelectNextSoldier) => ids.get(i + 1),
+                    Some(i) => ids.get(i.saturating_sub(1)),
+                    None => ids.first(),
+                };
+                if let Some(id) = next {
+                    *selected_soldier_id = *id;
+                    custom_fields.clear();
+                }
+            }
+            if let Message::ToggleFaceGallery = message {
+                *show_face_gallery = !*show_face_gallery;
+            }
+            if let Message::JumpToIssue { id, field } = message {
+                *selected_soldier_id = id;
+                *highlighted_field = Some(field);
+            }
+            if let Message::AddRecruit = message {
+                let pool = RecruitPool::new(&self.game_data, save);
+                let id = save.next_soldier_id();
+                let recruit = generator::recruit(id, &pool, &mut rand::thread_rng());
+                save.soldiers.push(recruit);
+                *selected_soldier_id = id;
+                *highlighted_field = None;
+                custom_fields.clear();
+            }
+            let roster_changed = match &message {
+                Message::ShowFiltered(filter) => {
+                    self.roster_filter = filter.clone();
+                    true
+                }
+                Message::UpdateSearch(search) => {
+                    self.roster_filter.search = search.clone();
+                    true
+                }
+                Message::GenderFilterSelected(gender) => {
+                    self.roster_filter.gender = gender.clone().into();
+                    true
+                }
+                Message::NationFilterSelected(nation) => {
+                    self.roster_filter.nation = nation.clone().into();
+                    true
+                }
+                Message::RoleFilterSelected(role) => {
+                    self.roster_filter.role = role.clone().into();
+                    true
+                }
+                Message::RankFilterSelected(rank) => {
+                    let ranks = self.game_data.ranks.all();
+                    self.roster_filter.xp = Option::<String>::from(rank.clone())
+                        .and_then(|name| ranks.iter().find(|rank| rank.name == name))
+                        .map(|rank| self.game_data.ranks.xp_range(rank));
+                    true
+                }
+                Message::SortKeySelected(key) => {
+                    self.sort_key = *key;
+                    true
+                }
+                Message::ToggleSortDirection => {
+                    self.sort_descending = !self.sort_descending;
+                    true
+                }
+                Message::SortByColumn(key) => {
+                    // Clicking the column already sorted by flips it
+                    if self.sort_key == *key {
+                        self.sort_descending = !self.sort_descending;
+                    } else {
+                        self.sort_key = *key;
+                        self.sort_descending = false;
+                    }
+                    true
+                }
+                _ => false,
+            };
+            if roster_changed {
+                // Keep the editor on a soldier the roster is showing
+                let visible = roster::roster(
+                    &save.soldiers,
+                    &self.roster_filter,
+                    self.sort_key,
+                    self.sort_descending,
+                );
+                if !visible
+                    .iter()
+                    .any(|soldier| soldier.id == *selected_soldier_id)
+                {
+                    if let Some(soldier) = visible.first() {
+                        *selected_soldier_id = soldier.id;
+                        *highlighted_field = None;
+                        custom_fields.clear();
+                    }
+                }
+            }
+            if let Message::MarkSoldier { id, marked } = message {
+                if marked {
+                    marked_soldier_ids.insert(id);
+                } else {
+                    marked_soldier_ids.remove(&id);
+                }
+            }
+            if let Message::RenameSoldier | Message::RenameMarked = message {
+                let ids = match message {
+                    Message::RenameSoldier => BTreeSet::from([*selected_soldier_id]),
+                    _ => marked_soldier_ids.clone(),
+                };
+                let mut rng = rand::thread_rng();
+                for soldier in save
+                    .soldiers
+                    .iter_mut()
+                    .filter(|soldier| ids.contains(&soldier.id))
+                {
+                    soldier.name = self.game_data.names.generate(
+                        &String::from_utf8_lossy(&soldier.nation),
+                        soldier.gender,
+                        &mut rng,
+                    );
+                }
+            }
+            if let Message::ImportNames = message {
+                return Command::perform(
+                    pick_file(
+                        "Select a list of names",
+                        "Text file",
+                        &["txt"],
+                        self.config.last_directory.clone(),
+                    ),
+                    Message::NamesFilePicked,
+                );
+            }
+            if let Message::NamesFilePicked(Some(path)) = message {
+                return Command::perform(read_names_file(path), Message::NamesFileLoaded);
+            }
+            if let Message::NamesFileLoaded(result) = &message {
+                match result {
+                    Ok(text) => {
+                        let pool = RecruitPool::new(&self.game_data, save);
+                        // Fill the ticked soldiers, or the whole roster if none are ticked
+                        let soldiers = save.soldiers.iter_mut().filter(|soldier| {
+                            marked_soldier_ids.is_empty()
+                                || marked_soldier_ids.contains(&soldier.id)
+                        });
+                        names::assign_names(
+                            soldiers,
+                            &names::parse_name_file(text),
+                            &pool.nationalities,
+                        );
+                    }
+                    Err(e) => return error_dialog("Could not read names file!", e),
+                }
+            }
+            if let Message::CopyStats { from, stat } = message {
+                let to: Vec<_> = marked_soldier_ids.iter().copied().collect();
+                compare::copy_stats_to(save, from, &to, stat);
+            }
+            if let Message::RerollSoldier = message {
+                let pool = RecruitPool::new(&self.game_data, save);
+                if let Some(soldier) = save.get_soldier_mut(*selected_soldier_id) {
+                    generator::reroll(soldier, &pool, &mut rand::thread_rng());
+                    custom_fields.clear();
+                }
             }
 
             if let Some(soldier) = save.get_soldier_mut(*selected_soldier_id) {
@@ -147,65 +1114,36 @@ impl Sandbox for Editor {
                     Message::GenderSelected(gender) => {
                         soldier.gender = gender;
                     }
+                    Message::ChoiceSelected(field, Choice::Key { key, .. }) => {
+                        custom_fields.remove(&field);
+                        match field {
+                            Field::Race => soldier.race = key.into_bytes(),
+                            Field::Nation => soldier.nation = key.into_bytes(),
+                            Field::Regiment => soldier.regiment = key.into_bytes(),
+                            Field::Experience => soldier.experience = key.into_bytes(),
+                            _ => {}
+                        }
+                    }
+                    Message::ChoiceSelected(field, Choice::Other) => {
+                        custom_fields.insert(field);
+                    }
                     Message::UpdateAge(val) => {
                         soldier.age = val;
                     }
                     Message::UpdateXP(val) => {
                         soldier.xp = val;
                     }
-                    Message::UpdateTimeUnits(val) => {
-                        if val < soldier.stats.time_units_original {
-                            return;
-                        }
-                        soldier.stats.time_units_current = val;
-                    }
-                    Message::UpdateTimeUnitsBase(val) => {
-                        soldier.stats.time_units_original = val;
-                    }
-                    Message::UpdateHealth(val) => {
-                        if val < soldier.stats.health_original {
-                            return;
-                        }
-                        soldier.stats.health_current = val;
+                    Message::RankSelected(rank) => {
+                        soldier.xp = rank.xp;
                     }
-                    Message::UpdateHealthBase(val) => {
-                        soldier.stats.health_original = val;
+                    Message::UpdateStat(stat, val) => {
+                        soldier.stats.set_current(stat, val);
                     }
-                    Message::UpdateStrength(val) => {
-                        if val < soldier.stats.strength_original {
-                            return;
-                        }
-                        soldier.stats.strength_current = val;
-                    }
-                    Message::UpdateStrengthBase(val) => {
-                        soldier.stats.strength_original = val;
-                    }
-                    Message::UpdateAccuracy(val) => {
-                        if val < soldier.stats.accuracy_original {
-                            return;
-                        }
-                        soldier.stats.accuracy_current = val;
-                    }
-                    Message::UpdateAccuracyBase(val) => {
-                        soldier.stats.accuracy_original = val;
-                    }
-                    Message::UpdateReflexes(val) => {
-                        if val < soldier.stats.reflexes_original {
-                            return;
-                        }
-                        soldier.stats.reflexes_current = val;
+                    Message::UpdateStatBase(stat, val) => {
+                        soldier.stats.set_original(stat, val);
                     }
-                    Message::UpdateReflexesBase(val) => {
-                        soldier.stats.reflexes_original = val;
-                    }
-                    Message::UpdateBravery(val) => {
-                        if val < soldier.stats.bravery_original {
-                            return;
-                        }
-                        soldier.stats.bravery_current = val;
-                    }
-                    Message::UpdateBraveryBase(val) => {
-                        soldier.stats.bravery_original = val;
+                    Message::UpdateDropshipSeat(val) => {
+                        soldier.dropship_seat = Some(val);
                     }
                     Message::UpdateFaceNumber(val) => {
                         soldier.face_number = val;
@@ -213,60 +1151,262 @@ impl Sandbox for Editor {
                     _ => {}
                 }
             }
+
+            *issues = validate::validate_with(save, &self.game_data.rules());
+        }
+
+        Command::none()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let mut subscriptions = vec![
+            event::listen_with(shortcut),
+            event::listen_with(window_event),
+        ];
+        if let Session::Save { path, .. } = &self.session {
+            subscriptions.push(watch::watch_file(path.clone(), Message::SaveChangedOnDisk));
         }
+        Subscription::batch(subscriptions)
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let file_controls = view_file_controls(self);
 
-        let editor_panes: Element<_> = match &self {
-            Editor::Save {
+        if self.show_shortcuts {
+            return column![file_controls, view_shortcuts()].into();
+        }
+        if let Some(browser) = &self.browser {
+            return column![file_controls, view_browser(browser)].into();
+        }
+
+        let editor_panes: Element<_> = match &self.session {
+            Session::Save { save, issues, .. } if self.view_mode == ViewMode::Dashboard => column![
+                view_dashboard(save, &self.game_data.ranks),
+                view_issues(save, issues),
+            ]
+            .into(),
+            Session::Save {
                 save,
                 selected_soldier_id,
+                issues,
+                highlighted_field,
+                custom_fields,
+                show_face_gallery,
+                marked_soldier_ids,
                 ..
-            } => row![
-                view_soldier_list(save, *selected_soldier_id),
-                match save.get_soldier(*selected_soldier_id as u32) {
-                    Some(soldier) => view_soldier_editor(soldier),
-                    None => text("Select a soldier to edit")
-                        .width(Length::Fill)
-                        .height(Length::Fill)
-                        .vertical_alignment(Vertical::Center)
-                        .horizontal_alignment(Horizontal::Center)
-                        .size(30)
-                        .into(),
-                }
+            } if self.view_mode == ViewMode::Table => column![
+                view_roster_controls(
+                    save,
+                    &self.roster_filter,
+                    &self.game_data.ranks,
+                    self.sort_key,
+                    self.sort_descending
+                ),
+                view_roster_table(
+                    roster::roster(
+                        &save.soldiers,
+                        &self.roster_filter,
+                        self.sort_key,
+                        self.sort_descending
+                    ),
+                    *selected_soldier_id,
+                    issues,
+                    self.sort_key,
+                    self.sort_descending,
+                    self.game_data.rules().stat_range,
+                ),
+                view_issues(save, issues),
             ]
             .into(),
-            Editor::NoData => text("Open a Xenonauts save file")
-                .width(Length::Fill)
-                .height(Length::Fill)
-                .vertical_alignment(Vertical::Center)
-                .horizontal_alignment(Horizontal::Center)
-                .size(30)
-                .into(),
+            Session::Save {
+                save,
+                selected_soldier_id,
+                issues,
+                highlighted_field,
+                custom_fields,
+                show_face_gallery,
+                marked_soldier_ids,
+                on_disk,
+                spans,
+                diff_base,
+                ..
+            } => column![
+                row![
+                    column![
+                        view_roster_controls(
+                            save,
+                            &self.roster_filter,
+                            &self.game_data.ranks,
+                            self.sort_key,
+                            self.sort_descending
+                        ),
+                        view_soldier_list(
+                            roster::roster(
+                                &save.soldiers,
+                                &self.roster_filter,
+                                self.sort_key,
+                                self.sort_descending
+                            ),
+                            *selected_soldier_id,
+                            marked_soldier_ids,
+                            &self.game_data.ranks
+                        ),
+                    ],
+                    match save.get_soldier(*selected_soldier_id) {
+                        _ if self.view_mode == ViewMode::Compare => {
+                            view_comparison(save, marked_soldier_ids)
+                        }
+                        _ if self.view_mode == ViewMode::Diff => {
+                            view_save_diff(save, diff_base.as_deref())
+                        }
+                        _ if self.view_mode == ViewMode::Charts => {
+                            view_squad_charts(roster::roster(
+                                &save.soldiers,
+                                &self.roster_filter,
+                                self.sort_key,
+                                self.sort_descending,
+                            ))
+                        }
+                        Some(soldier) if self.view_mode == ViewMode::Inspect => {
+                            view_hex_inspector(soldier, on_disk, spans)
+                        }
+                        Some(soldier) => view_soldier_editor(
+                            soldier,
+                            *highlighted_field,
+                            &self.game_data,
+                            custom_fields,
+                            *show_face_gallery
+                        ),
+                        None => text("Select a soldier to edit")
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                            .vertical_alignment(Vertical::Center)
+                            .horizontal_alignment(Horizontal::Center)
+                            .size(30)
+                            .into(),
+                    }
+                ]
+                .height(Length::Fill),
+                view_issues(save, issues),
+            ]
+            .into(),
+            Session::NoData => view_start_screen(
+                &self.config.recent_saves,
+                &self.recent_headers,
+                self.save_dialog_directory(),
+            ),
+        };
+
+        let reload_banner = match &self.session {
+            Session::Save {
+                newer_on_disk: Some(_),
+                ..
+            } => Some(view_reload_banner()),
+            _ => None,
         };
 
-        column![file_controls, editor_panes].into()
+        column![file_controls]
+            .push_maybe(reload_banner)
+            .push(editor_panes)
+            .into()
     }
 }
 
-fn view_file_controls(editor: &Editor) -> Element<Message> {
+fn view_file_controls(editor: &Editor) -> Element<'_, Message> {
     row![
         button(row![icon('\u{F3D8}'), "Open"].spacing(5))
             .padding(10)
             .on_press(Message::OpenFile),
-        text(match editor {
-            Editor::Save { path, .. } => path.as_os_str().to_str().unwrap_or(""),
-            Editor::NoData => "",
+        button(row![icon('\u{F52A}'), "Browse"].spacing(5))
+            .padding(10)
+            .on_press(Message::ToggleBrowser)
+            .style(if editor.browser.is_some() {
+                Button::Primary
+            } else {
+                Button::Secondary
+            }),
+        text(match &editor.session {
+            Session::Save { path, .. } => path.as_os_str().to_str().unwrap_or(""),
+            Session::NoData => "",
         })
         .size(20),
+        text(editor.status.as_deref().unwrap_or("")).size(14),
         horizontal_space().width(Length::Fill),
+        row![
+            button(row![icon('\u{F57F}'), "Dashboard"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Dashboard))
+                .style(if editor.view_mode == ViewMode::Dashboard {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F4E1}'), "Form"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Form))
+                .style(if editor.view_mode == ViewMode::Form {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F5AA}'), "Table"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Table))
+                .style(if editor.view_mode == ViewMode::Table {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F2CE}'), "Compare"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Compare))
+                .style(if editor.view_mode == ViewMode::Compare {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F17E}'), "Charts"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Charts))
+                .style(if editor.view_mode == ViewMode::Charts {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F34C}'), "Inspect"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Inspect))
+                .style(if editor.view_mode == ViewMode::Inspect {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+            button(row![icon('\u{F354}'), "Diff"].spacing(5))
+                .padding(10)
+                .on_press(Message::ViewModeSelected(ViewMode::Diff))
+                .style(if editor.view_mode == ViewMode::Diff {
+                    Button::Primary
+                } else {
+                    Button::Secondary
+                }),
+        ],
+        button(row![icon('\u{F2D4}'), "Game folder"].spacing(5))
+            .padding(10)
+            .on_press(Message::OpenGameFolder),
+        button(row![icon('\u{F3D7}'), "Add mod"].spacing(5))
+            .padding(10)
+            .on_press(Message::AddModFolder),
+        button(row![icon('\u{F64D}'), "Add recruit"].spacing(5))
+            .padding(10)
+            .on_press_maybe(match editor.session {
+                Session::Save { .. } => Some(Message::AddRecruit),
+                Session::NoData => None,
+            }),
         button(row![icon('\u{F7D8}'), "Save"].spacing(5))
             .padding(10)
-            .on_press_maybe(match editor {
-                Editor::Save { .. } => Some(Message::SaveFile),
-                Editor::NoData => None,
+            .on_press_maybe(match editor.session {
+                Session::Save { .. } => Some(Message::SaveFile),
+                Session::NoData => None,
             })
     ]
     .spacing(20)
@@ -275,190 +1415,1621 @@ fn view_file_controls(editor: &Editor) -> Element<Message> {
     .into()
 }
 
-fn view_soldier_list(save: &Save, selected_soldier_id: u32) -> Element<Message> {
-    scrollable(
-        keyed_column(save.soldiers.iter().map(|soldier| {
+/// Shown until a save is opened, with the saves opened most recently.
+fn view_start_screen<'a>(
+    recent_saves: &'a [PathBuf],
+    headers: &'a HashMap<PathBuf, SaveHeader>,
+    save_dir: Option<PathBuf>,
+) -> Element<'a, Message> {
+    let prompt = text("Open a Xenonauts save file")
+        .width(Length::Fill)
+        .horizontal_alignment(Horizontal::Center)
+        .size(30);
+    let save_folder = row![
+        text(match save_dir {
+            Some(dir) => format!("Saves are opened from {}", dir.display()),
+            None => String::from("No save folder found"),
+        }),
+        button(row![icon('\u{F3D8}'), "Change"].spacing(5))
+            .on_press(Message::OpenSaveFolder)
+            .style(Button::Secondary),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+    if recent_saves.is_empty() {
+        return container(
+            column![prompt, save_folder]
+                .spacing(20)
+                .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into();
+    }
+
+    let recent = column(recent_saves.iter().map(|path| {
+        let details: Element<_> = match headers.get(path) {
+            Some(header) => column![
+                text(&header.name).size(20),
+                text(format!(
+                    "{} in game, saved {}",
+                    header.game_time, header.saved_at
+                ))
+                .size(14),
+            ]
+            .into(),
+            None => text(
+                path.file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default(),
+            )
+            .size(20)
+            .into(),
+        };
+        button(column![details, text(path.display()).size(12)].spacing(5))
+            .width(Length::Fill)
+            .padding(10)
+            .style(Button::Secondary)
+            .on_press(Message::SaveFilePicked(Some(path.clone())))
+            .into()
+    }))
+    .spacing(10);
+
+    column![
+        prompt,
+        save_folder,
+        text("Recent saves").size(20),
+        scrollable(recent).height(Length::Fill),
+    ]
+    .spacing(20)
+    .padding(40)
+    .max_width(800)
+    .into()
+}
+
+fn view_reload_banner<'a>() -> Element<'a, Message> {
+    container(
+        row![
+            icon('\u{F33A}'),
+            text("The game has written a newer version of this save"),
+            horizontal_space().width(Length::Fill),
+            button(row![icon('\u{F116}'), "Reload"].spacing(5))
+                .on_press(Message::ReloadSave { keep_edits: false }),
+            button(row![icon('\u{F116}'), "Reload and keep my edits"].spacing(5))
+                .on_press(Message::ReloadSave { keep_edits: true }),
+            button("Ignore")
+                .on_press(Message::IgnoreNewerSave)
+                .style(Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .padding([5, 20])
+    .into()
+}
+
+fn view_browser(browser: &SaveBrowser) -> Element<'_, Message> {
+    let progress = match (browser.pending.len(), browser.unreadable) {
+        (0, 0) => String::new(),
+        (0, unreadable) => format!("{} files couldn't be read", unreadable),
+        (pending, _) => format!("Reading {} saves…", pending),
+    };
+    let controls = row![
+        text_input("Search names, files and mods", &browser.search)
+            .on_input(Message::UpdateBrowserSearch)
+            .width(Length::Fixed(320.0)),
+        text("Sort by"),
+        pick_list(
+            BrowserSortKey::ALL,
+            Some(browser.sort_key),
+            Message::BrowserSortKeySelected
+        ),
+        button(icon(if browser.sort_descending {
+            '\u{F128}'
+        } else {
+            '\u{F148}'
+        }))
+        .on_press(Message::ToggleBrowserSortDirection),
+        text(progress).size(14),
+        horizontal_space().width(Length::Fill),
+        button(row![icon('\u{F3D8}'), "Open"].spacing(5))
+            .padding(10)
+            .on_press_maybe(
+                browser
+                    .selected
+                    .clone()
+                    .map(|path| Message::SaveFilePicked(Some(path)))
+            ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let saves = browser::browse(
+        &browser.saves,
+        &browser.search,
+        browser.sort_key,
+        browser.sort_descending,
+    );
+    let rows = column(saves.into_iter().map(|summary| {
+        let (game_time, saved_at) = match &summary.header {
+            Some(header) => (header.game_time.as_str(), header.saved_at.as_str()),
+            None => ("", ""),
+        };
+        let details = row![
+            column![
+                text(summary.name()).size(20),
+                text(summary.path.display()).size(12),
+            ]
+            .width(Length::Fill),
+            text(game_time).width(Length::Fixed(150.0)),
+            text(saved_at).width(Length::Fixed(150.0)),
+            text(if summary.is_iron_man() {
+                "Iron Man"
+            } else {
+                ""
+            })
+            .width(Length::Fixed(80.0)),
+            text(format!("{} soldiers", summary.soldier_count)).width(Length::Fixed(100.0)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+        let mods = match summary.mods.len() {
+            0 => String::from("No mods"),
+            _ => format!("Mods: {}", summary.mods.join(", ")),
+        };
+        button(column![details, text(mods).size(12)].spacing(5))
+            .width(Length::Fill)
+            .padding(10)
+            .style(if browser.selected.as_ref() == Some(&summary.path) {
+                Button::Primary
+            } else {
+                Button::Secondary
+            })
+            .on_press(Message::BrowserSaveClicked(summary.path.clone()))
+            .into()
+    }))
+    .spacing(5);
+
+    column![controls, scrollable(rows).height(Length::Fill)]
+        .spacing(20)
+        .padding(20)
+        .into()
+}
+
+fn view_roster_controls<'a>(
+    save: &Save,
+    filter: &RosterFilter,
+    ranks: &Ranks,
+    sort_key: SortKey,
+    sort_descending: bool,
+) -> Element<'a, Message> {
+    let options = |values: BTreeSet<String>| {
+        std::iter::once(FilterChoice::Any)
+            .chain(values.into_iter().map(FilterChoice::Only))
+            .collect::<Vec<_>>()
+    };
+    let nations = save
+        .soldiers
+        .iter()
+        .map(|soldier| String::from_utf8_lossy(&soldier.nation).to_string())
+        .collect();
+    // Male and female, then any values only a mod knows that soldiers in this save have
+    let unknown_genders: BTreeSet<u8> = save
+        .soldiers
+        .iter()
+        .filter_map(|soldier| match soldier.gender {
+            Gender::Unknown(byte) => Some(byte),
+            _ => None,
+        })
+        .collect();
+    let genders = [
+        FilterChoice::Any,
+        FilterChoice::Only(Gender::Male),
+        FilterChoice::Only(Gender::Female),
+    ]
+    .into_iter()
+    .chain(
+        unknown_genders
+            .into_iter()
+            .map(|byte| FilterChoice::Only(Gender::Unknown(byte))),
+    )
+    .collect::<Vec<_>>();
+    let roles = save.soldiers.iter().filter_map(Soldier::role).collect();
+    let rank_names = ranks.all().iter().map(|rank| rank.name.clone()).collect();
+    let rank = ranks
+        .all()
+        .iter()
+        .find(|rank| filter.xp.as_ref() == Some(&ranks.xp_range(rank)))
+        .map(|rank| rank.name.clone());
+
+    column![
+        text_input("Search names, nationalities and regiments", &filter.search)
+            .on_input(Message::UpdateSearch),
+        row![
+            pick_list(
+                genders,
+                Some(FilterChoice::from(filter.gender)),
+                Message::GenderFilterSelected
+            ),
+            pick_list(
+                options(nations),
+                Some(FilterChoice::from(filter.nation.clone())),
+                Message::NationFilterSelected
+            ),
+            pick_list(
+                options(roles),
+                Some(FilterChoice::from(filter.role.clone())),
+                Message::RoleFilterSelected
+            ),
+            pick_list(
+                options(rank_names),
+                Some(FilterChoice::from(rank)),
+                Message::RankFilterSelected
+            ),
+        ]
+        .spacing(5),
+        row![
+            text("Sort by"),
+            pick_list(SortKey::all(), Some(sort_key), Message::SortKeySelected),
+            button(icon(if sort_descending {
+                '\u{F128}'
+            } else {
+                '\u{F148}'
+            }))
+            .on_press(Message::ToggleSortDirection),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(5)
+    .padding([10, 20, 0, 20])
+    .width(Length::Fixed(320.0))
+    .into()
+}
+
+fn view_soldier_list<'a>(
+    soldiers: Vec<&'a Soldier>,
+    selected_soldier_id: u32,
+    marked_soldier_ids: &BTreeSet<u32>,
+    ranks: &Ranks,
+) -> Element<'a, Message> {
+    let bulk_controls = row![
+        button(row![icon('\u{F116}'), "Rename ticked"].spacing(5))
+            .on_press_maybe((!marked_soldier_ids.is_empty()).then_some(Message::RenameMarked)),
+        button(row![icon('\u{F3D8}'), "Import names"].spacing(5)).on_press(Message::ImportNames),
+    ]
+    .spacing(5)
+    .padding([10, 20, 0, 20]);
+
+    let list = scrollable(
+        keyed_column(soldiers.into_iter().map(|soldier| {
+            let id = soldier.id;
             (
-                soldier.id,
-                button(text(soldier.name.as_str()))
-                    .on_press(Message::SelectSoldier { id: soldier.id })
-                    .style(if soldier.id == selected_soldier_id {
+                id,
+                row![
+                    checkbox("", marked_soldier_ids.contains(&id))
+                        .on_toggle(move |marked| Message::MarkSoldier { id, marked }),
+                    button(
+                        row![
+                            text(
+                                ranks
+                                    .rank_for(soldier.xp)
+                                    .map(|rank| rank.name.as_str())
+                                    .unwrap_or("")
+                            )
+                            .size(12),
+                            text(soldier.name.as_str()),
+                        ]
+                        .spacing(5)
+                        .align_items(Alignment::Center),
+                    )
+                    .on_press(Message::SelectSoldier { id })
+                    .style(if id == selected_soldier_id {
                         Button::Primary
                     } else {
                         Button::Text
-                    })
-                    .into(),
+                    }),
+                ]
+                .align_items(Alignment::Center)
+                .into(),
             )
         }))
         .spacing(5)
         .padding(20)
         .align_items(Alignment::End),
-    )
-    .into()
+    );
+
+    column![bulk_controls, list].into()
 }
 
-fn view_soldier_editor(soldier: &Soldier) -> Element<Message> {
-    column![
-        row![
-            column![
-                row![
-                    text("Name").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input("Soldier name", soldier.name.as_str()).on_input(Message::UpdateName),
-                ],
-                row![
-                    text("Age").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    number_input(soldier.age, f32::MAX, Message::UpdateAge).min(0.0).step(1.0),
-                    horizontal_space().width(Length::Fixed(20.0)),
-                    text("Gender").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    pick_list(
-                        [Gender::Male, Gender::Female],
-                        Some(soldier.gender),
-                        Message::GenderSelected
-                    ),
-                    horizontal_space().width(Length::Fixed(20.0)),
-                    text("XP").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    number_input(soldier.xp, u32::MAX, Message::UpdateXP).min(0),
-                ],
-                row![
-                    text("Nationality").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input("Soldier nationality", soldier.nationality.as_str())
-                        .on_input(Message::UpdateNationality),
-                    horizontal_space().width(Length::Fixed(20.0)),
-                    text("Flag").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input("Soldier flag", &String::from_utf8(soldier.nation.clone()).unwrap())
-                        .on_input(Message::UpdateFlag),
-                ],
+/// Columns of the roster table, with the key clicking their header sorts by and their width.
+/// The table's columns before the stats
+const TABLE_COLUMNS: [(&str, SortKey, f32); 6] = [
+    ("ID", SortKey::Id, 60.0),
+    ("Name", SortKey::Name, 180.0),
+    ("Nationality", SortKey::Nationality, 150.0),
+    ("Gender", SortKey::Gender, 110.0),
+    ("Age", SortKey::Age, 90.0),
+    ("XP", SortKey::Xp, 90.0),
+];
+const STAT_COLUMN_WIDTH: f32 = 80.0;
+
+/// Every column, with a column for each stat's current value followed by one for its base value.
+fn table_columns() -> Vec<(String, SortKey, f32)> {
+    TABLE_COLUMNS
+        .iter()
+        .map(|(label, key, width)| (label.to_string(), *key, *width))
+        .chain(Stat::ALL.into_iter().flat_map(|stat| {
+            [
+                (
+                    stat.short_name().to_string(),
+                    SortKey::Current(stat),
+                    STAT_COLUMN_WIDTH,
+                ),
+                (
+                    format!("{} base", stat.short_name()),
+                    SortKey::Base(stat),
+                    STAT_COLUMN_WIDTH,
+                ),
             ]
-            .spacing(10),
-            column![
-                row![
-                    text("Race").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input(
-                        "Soldier race",
-                        &String::from_utf8(soldier.race.clone()).unwrap()
-                    )
-                    .width(50)
-                    .on_input(Message::UpdateRace),
-                    horizontal_space().width(Length::Fixed(20.0)),
-                    text("Face").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    number_input(soldier.face_number, u32::MAX, Message::UpdateFaceNumber).min(0),
-                ],
-                row![
-                    text("Regiment").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input(
-                        "Soldier regiment",
-                        &String::from_utf8(soldier.regiment.clone()).unwrap()
-                    )
-                    .width(150)
-                    .on_input(Message::UpdateRegiment),
-                ],
+        }))
+        .collect()
+}
+
+fn view_roster_table<'a>(
+    soldiers: Vec<&'a Soldier>,
+    selected_soldier_id: u32,
+    issues: &[Issue],
+    sort_key: SortKey,
+    sort_descending: bool,
+    stat_range: RangeInclusive<u32>,
+) -> Element<'a, Message> {
+    let header = row(table_columns().into_iter().map(|(label, key, width)| {
+        let mut label = row![text(label)].spacing(5);
+        if key == sort_key {
+            label = label.push(icon(if sort_descending {
+                '\u{F128}'
+            } else {
+                '\u{F148}'
+            }));
+        }
+        button(label)
+            .on_press(Message::SortByColumn(key))
+            .style(Button::Text)
+            .width(Length::Fixed(width))
+            .into()
+    }));
+
+    let rows = keyed_column(soldiers.into_iter().map(|soldier| {
+        let severity = issues
+            .iter()
+            .filter(|issue| issue.soldier_id == soldier.id)
+            .map(|issue| issue.severity)
+            .max();
+        (
+            soldier.id,
+            view_roster_table_row(soldier, selected_soldier_id, severity, &stat_range),
+        )
+    }))
+    .spacing(2);
+
+    scrollable(column![header, rows].spacing(5).padding(10))
+        .direction(Direction::Both {
+            vertical: Properties::default(),
+            horizontal: Properties::default(),
+        })
+        .height(Length::Fill)
+        .into()
+}
+
+fn view_roster_table_row<'a>(
+    soldier: &'a Soldier,
+    selected_soldier_id: u32,
+    severity: Option<Severity>,
+    stat_range: &RangeInclusive<u32>,
+) -> Element<'a, Message> {
+    let id = soldier.id;
+    let width = |column: usize| Length::Fixed(TABLE_COLUMNS[column].2);
+
+    let cells = row![
+        button(text(id))
+            .on_press(Message::SelectSoldier { id })
+            .style(if id == selected_soldier_id {
+                Button::Primary
+            } else {
+                Button::Text
+            })
+            .width(width(0)),
+        text_input("Name", &soldier.name)
+            .on_input(move |name| edit_soldier(id, Message::UpdateName(name)))
+            .width(width(1)),
+        text_input("Nationality", &soldier.nationality)
+            .on_input(move |nationality| {
+                edit_soldier(id, Message::UpdateNationality(nationality))
+            })
+            .width(width(2)),
+        pick_list(
+            gender_choices(soldier.gender),
+            Some(soldier.gender),
+            move |gender| edit_soldier(id, Message::GenderSelected(gender))
+        )
+        .width(width(3)),
+        number_input(soldier.age, f32::MAX, move |age| {
+            edit_soldier(id, Message::UpdateAge(age))
+        })
+        .min(0.0)
+        .step(1.0)
+        .width(width(4)),
+        number_input(soldier.xp, u32::MAX, move |xp| edit_soldier(
+            id,
+            Message::UpdateXP(xp)
+        ))
+        .min(0)
+        .width(width(5)),
+    ];
+    // Each stat has a column for its current value followed by one for its base value
+    let cells = soldier
+        .stats
+        .iter()
+        .fold(cells, |cells, (stat, current, base)| {
+            cells
+                .push(
+                    number_input(current, *stat_range.end(), move |value| {
+                        edit_soldier(id, Message::UpdateStat(stat, value))
+                    })
+                    .min(*stat_range.start())
+                    .width(Length::Fixed(STAT_COLUMN_WIDTH)),
+                )
+                .push(
+                    number_input(base, current, move |value| {
+                        edit_soldier(id, Message::UpdateStatBase(stat, value))
+                    })
+                    .min(0)
+                    .width(Length::Fixed(STAT_COLUMN_WIDTH)),
+                )
+        })
+        .align_items(Alignment::Center);
+
+    container(cells)
+        .style(match severity {
+            Some(Severity::Error) => theme::Container::Custom(Box::new(RowHighlight(
+                Color::from_rgba(0.9, 0.1, 0.1, 0.25),
+            ))),
+            Some(Severity::Warning) => theme::Container::Custom(Box::new(RowHighlight(
+                Color::from_rgba(0.9, 0.5, 0.0, 0.25),
+            ))),
+            None => theme::Container::Transparent,
+        })
+        .into()
+}
+
+/// Tints a table row that has validation issues, or the bytes of a part of a soldier's record.
+struct RowHighlight(Color);
+
+impl container::StyleSheet for RowHighlight {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(self.0.into()),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+/// Bytes shown on each row of the hex inspector
+const HEX_ROW_LENGTH: usize = 16;
+
+/// Decoded parts of a record cycle through these, so neighbouring parts can be told apart
+const PART_COLORS: [Color; 3] = [
+    Color::from_rgba(0.2, 0.4, 0.8, 0.25),
+    Color::from_rgba(0.2, 0.7, 0.3, 0.25),
+    Color::from_rgba(0.6, 0.3, 0.8, 0.25),
+];
+const UNDECODED_COLOR: Color = Color::from_rgba(0.9, 0.5, 0.0, 0.45);
+
+/// A read-only hex dump of the soldier's record, with each part tinted and listed alongside.
+/// Hovering over a byte shows what it might be, and where it is in the file if the soldier
+/// hasn't been edited since the save was last loaded or written.
+fn view_hex_inspector<'a>(
+    soldier: &Soldier,
+    on_disk: &[u8],
+    spans: &SpanMap,
+) -> Element<'a, Message> {
+    let record = inspect::Record::new(soldier);
+    let file_start = spans
+        .get(soldier.id, "Start marker")
+        .map(|span| span.start)
+        .filter(|start| on_disk.get(*start..start + record.bytes.len()) == Some(&record.bytes[..]));
+    let color = |index: usize, part: &inspect::Part| {
+        if part.decoded {
+            PART_COLORS[index % PART_COLORS.len()]
+        } else {
+            UNDECODED_COLOR
+        }
+    };
+    let part_index = |offset: usize| {
+        record
+            .parts
+            .iter()
+            .position(|part| part.range.contains(&offset))
+    };
+
+    let byte_cell = |offset: usize| -> Element<'a, Message> {
+        let cell = container(text(format!("{:02X}", record.bytes[offset])).font(Font::MONOSPACE))
+            .padding([2, 4]);
+        let Some(index) = part_index(offset) else {
+            return cell.into();
+        };
+        let part = &record.parts[index];
+        let reading = inspect::read_at(&record, offset);
+        let details = column![
+            text(part.name).size(18),
+            text(format!(
+                "Byte {} of {}–{}",
+                offset,
+                part.range.start,
+                part.range.end - 1
+            )),
+            text(format!(
+                "u32: {}",
+                reading
+                    .u32
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            )),
+            text(format!(
+                "f32: {}",
+                reading
+                    .f32
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            )),
+            text(format!("String: \"{}\"", reading.text)),
+        ]
+        .push_maybe(file_start.map(|start| text(format!("Byte {} of the file", start + offset))))
+        .spacing(2);
+        tooltip(
+            cell.style(theme::Container::Custom(Box::new(RowHighlight(color(
+                index, part,
+            ))))),
+            container(details).padding(10).style(theme::Container::Box),
+            tooltip::Position::FollowCursor,
+        )
+        .into()
+    };
+
+    let rows = column(
+        (0..record.bytes.len())
+            .step_by(HEX_ROW_LENGTH)
+            .map(|start| {
+                let end = (start + HEX_ROW_LENGTH).min(record.bytes.len());
+                let ascii: String = record.bytes[start..end]
+                    .iter()
+                    .map(|byte| {
+                        if byte.is_ascii_graphic() || *byte == b' ' {
+                            *byte as char
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
                 row![
-                    text("Experience").size(20),
-                    horizontal_space().width(Length::Fixed(10.0)),
-                    text_input(
-                        "Soldier experience",
-                        &String::from_utf8(soldier.experience.clone()).unwrap()
-                    )
-                    .width(150)
-                    .on_input(Message::UpdateExperience),
-                ],
-            ].spacing(10)
-        ].spacing(20),
-        view_soldier_stats_editor(&soldier.stats),
+                    text(format!("{:04X}", start))
+                        .font(Font::MONOSPACE)
+                        .width(Length::Fixed(50.0)),
+                    row((start..end).map(byte_cell)),
+                    text(ascii).font(Font::MONOSPACE),
+                ]
+                .spacing(10)
+                .into()
+            }),
+    );
+
+    let legend = column(record.parts.iter().enumerate().map(|(index, part)| {
+        row![
+            container(horizontal_space().width(Length::Fixed(12.0)))
+                .height(Length::Fixed(12.0))
+                .style(theme::Container::Custom(Box::new(RowHighlight(color(
+                    index, part
+                ))))),
+            text(format!(
+                "{}: {}–{}",
+                part.name,
+                part.range.start,
+                part.range.end - 1
+            )),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .into()
+    }))
+    .spacing(2);
+
+    row![
+        scrollable(rows).width(Length::Fill),
+        scrollable(legend).width(Length::Fixed(280.0)),
     ]
     .spacing(20)
+    .padding(20)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn edit_soldier(id: u32, edit: Message) -> Message {
+    Message::EditSoldier {
+        id,
+        edit: Box::new(edit),
+    }
+}
+
+/// The changes since an earlier save, soldier by soldier, for working out what the undecoded
+/// bytes mean by saving either side of a mission.
+fn view_save_diff<'a>(save: &'a Save, base: Option<&(PathBuf, Save)>) -> Element<'a, Message> {
+    let pick = button(row![icon('\u{F354}'), "Compare with an earlier save"].spacing(5))
+        .on_press(Message::PickDiffSave)
+        .style(Button::Secondary);
+    let Some((path, earlier)) = base else {
+        return container(
+            column![
+                text("Pick an earlier save to see what has changed since").size(30),
+                pick
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into();
+    };
+
+    let changes = diff::diff_saves(earlier, save);
+    let name = |id: u32, save: &Save| {
+        save.get_soldier(id)
+            .map(|soldier| soldier.name.clone())
+            .unwrap_or_default()
+    };
+    let mut lines = column![].spacing(5);
+    if changes.is_empty() {
+        lines = lines.push(text("No soldiers have changed"));
+    }
+    for id in &changes.added {
+        lines = lines.push(
+            text(format!("soldier {}: {} joined", id, name(*id, save)))
+                .style(Color::from_rgb(0.1, 0.6, 0.1)),
+        );
+    }
+    for id in &changes.removed {
+        lines = lines.push(
+            text(format!("soldier {}: {} is gone", id, name(*id, earlier)))
+                .style(Color::from_rgb(0.8, 0.2, 0.2)),
+        );
+    }
+    for soldier in &changes.changed {
+        lines = lines.push(
+            button(text(soldier.name.as_str()).size(20))
+                .on_press(Message::SelectSoldier { id: soldier.id })
+                .style(Button::Text),
+        );
+        for (line, change) in soldier.lines().into_iter().zip(&soldier.changes) {
+            let line = text(line).font(Font::MONOSPACE);
+            lines = lines.push(match change {
+                diff::Change::Byte { .. } => line.style(Color {
+                    a: 1.0,
+                    ..UNDECODED_COLOR
+                }),
+                diff::Change::Field { .. } => line,
+            });
+        }
+    }
+
+    column![
+        row![
+            text(format!("Changes since {}", path.display())).size(20),
+            pick
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center),
+        scrollable(lines).width(Length::Fill),
+    ]
+    .spacing(10)
     .padding(10)
     .into()
 }
 
-fn view_soldier_stats_editor(stats: &SoldierStats) -> Element<Message> {
+fn view_comparison<'a>(save: &'a Save, marked_soldier_ids: &BTreeSet<u32>) -> Element<'a, Message> {
+    let soldiers: Vec<_> = marked_soldier_ids
+        .iter()
+        .filter_map(|id| save.get_soldier(*id))
+        .collect();
+    if soldiers.len() < 2 {
+        return text("Tick two or more soldiers in the roster to compare them")
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center)
+            .size(30)
+            .into();
+    }
+
+    let label_width = Length::Fixed(150.0);
+    let soldier_width = Length::Fixed(200.0);
+
+    let header = row(
+        std::iter::once(horizontal_space().width(label_width).into()).chain(soldiers.iter().map(
+            |soldier| {
+                column![
+                    text(soldier.name.as_str()).size(20),
+                    button(row![icon('\u{F3C2}'), "Copy all stats"].spacing(5))
+                        .on_press(Message::CopyStats {
+                            from: soldier.id,
+                            stat: None
+                        })
+                        .style(Button::Secondary),
+                ]
+                .spacing(5)
+                .width(soldier_width)
+                .into()
+            },
+        )),
+    );
+
+    let rows = compare::compare(&soldiers).into_iter().map(|comparison| {
+        let deltas = comparison.deltas();
+        let cells = soldiers
+            .iter()
+            .zip(comparison.values.iter().zip(deltas))
+            .enumerate()
+            .map(|(i, (soldier, ((current, original), delta)))| {
+                let value = text(format!("{current} ({original})")).size(20);
+                let value = if *current == comparison.best {
+                    value.style(Color::from_rgb(0.1, 0.6, 0.1))
+                } else {
+                    value
+                };
+                let delta = match (i, delta) {
+                    (0, _) => String::new(),
+                    (_, delta) if delta > 0 => format!("+{delta}"),
+                    (_, delta) => delta.to_string(),
+                };
+                row![
+                    value,
+                    text(delta).size(14),
+                    button(icon('\u{F3C2}'))
+                        .on_press(Message::CopyStats {
+                            from: soldier.id,
+                            stat: Some(comparison.stat),
+                        })
+                        .style(Button::Text),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .width(soldier_width)
+                .into()
+            });
+        row(std::iter::once(
+            container(field_label(Field::Stat(comparison.stat), None))
+                .width(label_width)
+                .into(),
+        )
+        .chain(cells))
+        .align_items(Alignment::Center)
+        .into()
+    });
+
+    scrollable(
+        column(std::iter::once(header.into()).chain(rows))
+            .spacing(10)
+            .padding(20),
+    )
+    .direction(Direction::Both {
+        vertical: Properties::default(),
+        horizontal: Properties::default(),
+    })
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn view_issues<'a>(save: &'a Save, issues: &'a [Issue]) -> Element<'a, Message> {
+    let header = match issues.len() {
+        0 => String::from("No issues found"),
+        1 => String::from("1 issue"),
+        n => format!("{n} issues"),
+    };
+
     column![
-        view_soldier_stats_editor_row(
-            "Time units",
-            stats.time_units_current,
-            Message::UpdateTimeUnits,
-            stats.time_units_original,
-            Message::UpdateTimeUnitsBase,
-        ),
-        view_soldier_stats_editor_row(
-            "Health",
-            stats.health_current,
-            Message::UpdateHealth,
-            stats.health_original,
-            Message::UpdateHealthBase,
-        ),
-        view_soldier_stats_editor_row(
-            "Strength",
-            stats.strength_current,
-            Message::UpdateStrength,
-            stats.strength_original,
-            Message::UpdateStrengthBase,
-        ),
-        view_soldier_stats_editor_row(
-            "Accuracy",
-            stats.accuracy_current,
-            Message::UpdateAccuracy,
-            stats.accuracy_original,
-            Message::UpdateAccuracyBase,
+        text(header).size(20),
+        scrollable(
+            column(issues.iter().map(|issue| {
+                let name = save
+                    .get_soldier(issue.soldier_id)
+                    .map(|soldier| soldier.name.as_str())
+                    .unwrap_or("");
+                button(
+                    row![
+                        icon(match issue.severity {
+                            Severity::Warning => '\u{F33A}',
+                            Severity::Error => '\u{F622}',
+                        }),
+                        text(format!("{name}: {}", issue.message)),
+                    ]
+                    .spacing(5),
+                )
+                .on_press(Message::JumpToIssue {
+                    id: issue.soldier_id,
+                    field: issue.field,
+                })
+                .style(Button::Text)
+                .into()
+            }))
+            .width(Length::Fill),
+        )
+        .height(Length::Fixed(120.0)),
+    ]
+    .spacing(5)
+    .padding(10)
+    .into()
+}
+
+fn view_soldier_editor<'a>(
+    soldier: &'a Soldier,
+    highlighted: Option<Field>,
+    game_data: &'a GameData,
+    custom_fields: &HashSet<Field>,
+    show_face_gallery: bool,
+) -> Element<'a, Message> {
+    let rank = game_data.ranks.rank_for(soldier.xp);
+    let race = String::from_utf8_lossy(&soldier.race).to_string();
+    let nationality = game_data.get_nationality(&String::from_utf8_lossy(&soldier.nation));
+    let regiments = match nationality {
+        Some(nationality) if !nationality.regiments.is_empty() => {
+            nationality.regiments.iter().cloned().collect()
+        }
+        _ => game_data.regiments(),
+    };
+    let experiences = match nationality {
+        Some(nationality) if !nationality.experiences.is_empty() => {
+            nationality.experiences.iter().cloned().collect()
+        }
+        _ => game_data.experiences(),
+    };
+
+    let form = row![
+        column![
+            row![
+                field_label(Field::Name, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                text_input("Soldier name", soldier.name.as_str()).on_input(Message::UpdateName),
+                horizontal_space().width(Length::Fixed(10.0)),
+                button(row![icon('\u{F116}'), "New name"].spacing(5))
+                    .on_press(Message::RenameSoldier),
+                horizontal_space().width(Length::Fixed(10.0)),
+                button(row![icon('\u{F544}'), "Reroll"].spacing(5))
+                    .on_press(Message::RerollSoldier),
+                horizontal_space().width(Length::Fixed(10.0)),
+                button(row![icon('\u{F30A}'), "Export"].spacing(5))
+                    .on_press(Message::ExportSoldier),
+            ],
+            row![
+                field_label(Field::Age, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                number_input(soldier.age, f32::MAX, Message::UpdateAge)
+                    .min(0.0)
+                    .step(1.0),
+                horizontal_space().width(Length::Fixed(20.0)),
+                field_label(Field::Gender, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                pick_list(
+                    gender_choices(soldier.gender),
+                    Some(soldier.gender),
+                    Message::GenderSelected
+                ),
+                horizontal_space().width(Length::Fixed(20.0)),
+                field_label(Field::Xp, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                number_input(soldier.xp, u32::MAX, Message::UpdateXP).min(0),
+            ],
+            row![
+                text("Rank").size(20),
+                horizontal_space().width(Length::Fixed(10.0)),
+                pick_list(game_data.ranks.all(), rank.cloned(), Message::RankSelected),
+                horizontal_space().width(Length::Fixed(10.0)),
+                text(rank.map(|rank| rank.insignia.as_str()).unwrap_or("")),
+            ]
+            .align_items(Alignment::Center),
+            row![
+                field_label(Field::Nationality, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                text_input("Soldier nationality", soldier.nationality.as_str())
+                    .on_input(Message::UpdateNationality),
+                horizontal_space().width(Length::Fixed(20.0)),
+                field_label(Field::Nation, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                view_key_input(
+                    "Soldier flag",
+                    &game_data.strings,
+                    &soldier.nation,
+                    game_data.flags(),
+                    Field::Nation,
+                    custom_fields,
+                    Message::UpdateFlag
+                ),
+            ],
+        ]
+        .spacing(10),
+        column![
+            row![
+                field_label(Field::Race, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                view_key_input(
+                    "Soldier race",
+                    &game_data.strings,
+                    &soldier.race,
+                    game_data.races(),
+                    Field::Race,
+                    custom_fields,
+                    Message::UpdateRace
+                ),
+                horizontal_space().width(Length::Fixed(20.0)),
+                field_label(Field::FaceNumber, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                number_input(soldier.face_number, u32::MAX, Message::UpdateFaceNumber).min(0),
+            ],
+            row![
+                field_label(Field::Regiment, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                view_key_input(
+                    "Soldier regiment",
+                    &game_data.strings,
+                    &soldier.regiment,
+                    regiments,
+                    Field::Regiment,
+                    custom_fields,
+                    Message::UpdateRegiment
+                ),
+            ],
+            row![
+                field_label(Field::Experience, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                view_key_input(
+                    "Soldier experience",
+                    &game_data.strings,
+                    &soldier.experience,
+                    experiences,
+                    Field::Experience,
+                    custom_fields,
+                    Message::UpdateExperience
+                ),
+            ],
+            row![
+                text("Dropship").size(20),
+                horizontal_space().width(Length::Fixed(10.0)),
+                text(String::from_utf8_lossy(&soldier.carrier)),
+                horizontal_space().width(Length::Fixed(20.0)),
+                field_label(Field::DropshipSeat, highlighted),
+                horizontal_space().width(Length::Fixed(10.0)),
+                match soldier.dropship_seat {
+                    // The last seat number means no seat at all
+                    Some(seat) => number_input(seat, u32::MAX - 1, Message::UpdateDropshipSeat)
+                        .min(0)
+                        .into(),
+                    None => Element::from(text("Not on a dropship")),
+                },
+            ]
+            .align_items(Alignment::Center),
+            row![
+                text("Unknown stat").size(20),
+                horizontal_space().width(Length::Fixed(10.0)),
+                text(format!(
+                    "{} ({})",
+                    soldier
+                        .unknown_stat()
+                        .iter()
+                        .map(|byte| format!("{:02X}", byte))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    u32::from_le_bytes(soldier.unknown_stat())
+                ))
+                .font(Font::MONOSPACE),
+            ]
+            .align_items(Alignment::Center),
+        ]
+        .spacing(10)
+    ]
+    .spacing(20);
+
+    let mut editor = if game_data.portraits.is_empty() {
+        column![form]
+    } else {
+        let portrait: Element<_> =
+            match game_data
+                .portraits
+                .get(&race, soldier.gender, soldier.face_number)
+            {
+                Some(path) => image(path.to_path_buf()).width(Length::Fixed(96.0)).into(),
+                None => text("No portrait").width(Length::Fixed(96.0)).into(),
+            };
+        column![row![
+            column![
+                portrait,
+                button("Choose face").on_press(Message::ToggleFaceGallery)
+            ]
+            .spacing(5)
+            .align_items(Alignment::Center),
+            form,
+        ]
+        .spacing(20)]
+    }
+    .spacing(20)
+    .padding(10);
+
+    if show_face_gallery {
+        editor = editor.push(view_face_gallery(
+            &game_data.portraits,
+            &race,
+            soldier.gender,
+            soldier.face_number,
+        ));
+    }
+
+    editor
+        .push(
+            row![
+                view_soldier_stats_editor(
+                    &soldier.stats,
+                    highlighted,
+                    game_data.rules().stat_range
+                ),
+                canvas(RadarChart {
+                    stats: soldier.stats.clone()
+                })
+                .width(Length::Fixed(280.0))
+                .height(Length::Fixed(280.0)),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        )
+        .into()
+}
+
+fn view_dashboard<'a>(save: &'a Save, ranks: &Ranks) -> Element<'a, Message> {
+    let summary = dashboard::summarise(save, ranks);
+    let name = |id: u32| {
+        save.get_soldier(id)
+            .map(|soldier| soldier.name.as_str())
+            .unwrap_or("")
+    };
+    let soldier_button = |id: u32, label: String| -> Element<'a, Message> {
+        button(text(label))
+            .on_press(Message::OpenSoldier { id })
+            .style(Button::Text)
+            .into()
+    };
+    let count = |soldier_ids: &[u32]| match soldier_ids.len() {
+        1 => String::from("1 soldier"),
+        n => format!("{n} soldiers"),
+    };
+
+    let headcount = text(format!(
+        "{} soldiers: {} on dropships, {} in reserve",
+        summary.headcount,
+        summary.assigned,
+        summary.headcount - summary.assigned
+    ))
+    .size(24);
+
+    let stats = column(summary.stats.iter().map(|stat| {
+        row![
+            container(text(stat.stat).size(20)).width(Length::Fixed(120.0)),
+            text(format!("avg {:.1}", stat.average)).width(Length::Fixed(90.0)),
+            soldier_button(
+                stat.min.soldier_id,
+                format!("min {} ({})", stat.min.value, name(stat.min.soldier_id))
+            ),
+            soldier_button(
+                stat.max.soldier_id,
+                format!("max {} ({})", stat.max.value, name(stat.max.soldier_id))
+            ),
+            text("Top:"),
+            row(stat.top.iter().map(|top| soldier_button(
+                top.soldier_id,
+                format!("{} {}", name(top.soldier_id), top.value)
+            ))),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .into()
+    }))
+    .spacing(5);
+
+    let ranks = column(
+        std::iter::once(text("Ranks").size(20).into()).chain(
+            summary
+                .ranks
+                .iter()
+                .filter(|group| !group.soldier_ids.is_empty())
+                .map(|group| {
+                    let xp = ranks
+                        .all()
+                        .iter()
+                        .find(|rank| rank.name == group.key)
+                        .map(|rank| ranks.xp_range(rank));
+                    button(text(format!(
+                        "{}: {}",
+                        group.key,
+                        count(&group.soldier_ids)
+                    )))
+                    .on_press(Message::ShowFiltered(RosterFilter {
+                        xp,
+                        ..RosterFilter::default()
+                    }))
+                    .style(Button::Text)
+                    .into()
+                }),
         ),
-        view_soldier_stats_editor_row(
-            "Reflexes",
-            stats.reflexes_current,
-            Message::UpdateReflexes,
-            stats.accuracy_original,
-            Message::UpdateReflexesBase,
+    );
+
+    let nationalities = column(
+        std::iter::once(text("Nationalities").size(20).into()).chain(
+            summary.nationalities.iter().map(|group| {
+                let nationality = save
+                    .get_soldier(group.soldier_ids[0])
+                    .map(|soldier| soldier.nationality.as_str())
+                    .unwrap_or(group.key.as_str());
+                button(text(format!(
+                    "{nationality}: {}",
+                    count(&group.soldier_ids)
+                )))
+                .on_press(Message::ShowFiltered(RosterFilter {
+                    nation: Some(group.key.clone()),
+                    ..RosterFilter::default()
+                }))
+                .style(Button::Text)
+                .into()
+            }),
         ),
-        view_soldier_stats_editor_row(
-            "Bravery",
-            stats.bravery_current,
-            Message::UpdateBravery,
-            stats.bravery_original,
-            Message::UpdateBraveryBase,
+    );
+
+    let genders = column(
+        std::iter::once(text("Genders").size(20).into()).chain(
+            summary
+                .genders
+                .iter()
+                .filter(|group| !group.soldier_ids.is_empty())
+                .map(|group| {
+                    button(text(format!(
+                        "{}: {}",
+                        group.key,
+                        count(&group.soldier_ids)
+                    )))
+                    .on_press(Message::ShowFiltered(RosterFilter {
+                        gender: Some(group.key),
+                        ..RosterFilter::default()
+                    }))
+                    .style(Button::Text)
+                    .into()
+                }),
         ),
-    ]
+    );
+
+    let dropships = column(std::iter::once(text("Dropships").size(20).into()).chain(
+        summary.dropships.iter().map(|group| {
+            column![
+                text(format!("{}: {}", group.key, count(&group.soldier_ids))),
+                row(group
+                    .soldier_ids
+                    .iter()
+                    .map(|id| soldier_button(*id, name(*id).to_string()))),
+            ]
+            .into()
+        }),
+    ))
+    .spacing(5);
+
+    scrollable(
+        column![
+            headcount,
+            stats,
+            row![ranks, nationalities, genders].spacing(40),
+            dropships,
+        ]
+        .spacing(20)
+        .padding(20),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn view_squad_charts<'a>(soldiers: Vec<&Soldier>) -> Element<'a, Message> {
+    if soldiers.is_empty() {
+        return text("No soldiers match the roster filters")
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center)
+            .size(30)
+            .into();
+    }
+
+    let charts: Vec<Element<_>> = Stat::ALL
+        .into_iter()
+        .map(|stat| {
+            column![
+                text(stat).size(20),
+                canvas(Histogram {
+                    bins: charts::histogram(
+                        soldiers.iter().map(|soldier| soldier.stats.current(stat)),
+                        10,
+                    ),
+                })
+                .width(Length::Fixed(320.0))
+                .height(Length::Fixed(180.0)),
+            ]
+            .spacing(5)
+            .into()
+        })
+        .collect();
+
+    let mut charts = charts.into_iter();
+    scrollable(
+        column![
+            text(format!("Current stats of {} soldiers", soldiers.len())).size(20),
+            row(charts.by_ref().take(3)).spacing(30),
+            row(charts).spacing(30),
+        ]
+        .spacing(20)
+        .padding(20),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn view_face_gallery<'a>(
+    portraits: &Portraits,
+    race: &str,
+    gender: Gender,
+    selected_face: u32,
+) -> Element<'a, Message> {
+    scrollable(
+        row(portraits.faces(race, gender).map(|(face_number, path)| {
+            button(
+                column![
+                    image(path.to_path_buf()).height(Length::Fixed(80.0)),
+                    text(face_number),
+                ]
+                .align_items(Alignment::Center),
+            )
+            .on_press(Message::UpdateFaceNumber(face_number))
+            .style(if face_number == selected_face {
+                Button::Primary
+            } else {
+                Button::Text
+            })
+            .into()
+        }))
+        .spacing(5),
+    )
+    .direction(Direction::Horizontal(Properties::default()))
+    .into()
+}
+
+fn view_soldier_stats_editor(
+    stats: &SoldierStats,
+    highlighted: Option<Field>,
+    stat_range: RangeInclusive<u32>,
+) -> Element<'_, Message> {
+    column(stats.iter().map(|(stat, current, base)| {
+        view_soldier_stats_editor_row(stat, highlighted, current, base, stat_range.clone())
+    }))
     .spacing(10)
     .into()
 }
 
-fn view_soldier_stats_editor_row(
-    stat_name: &str,
+fn view_soldier_stats_editor_row<'a>(
+    stat: Stat,
+    highlighted: Option<Field>,
     current: u32,
-    update_current: fn(u32) -> Message,
     base: u32,
-    update_base: fn(u32) -> Message,
-) -> Element<Message> {
+    stat_range: RangeInclusive<u32>,
+) -> Element<'a, Message> {
     row![
-        text(stat_name).size(20),
+        button(field_label(Field::Stat(stat), highlighted))
+            .on_press(Message::FocusStat(stat))
+            .style(Button::Text)
+            .padding(0),
         horizontal_space().width(Length::Fixed(10.0)),
         text(current)
             .size(20)
             .width(Length::Fixed(30.0))
             .horizontal_alignment(Horizontal::Center),
         horizontal_space().width(Length::Fixed(10.0)),
-        slider(1..=100, current, update_current),
+        slider(stat_range, current, move |value| Message::UpdateStat(
+            stat, value
+        )),
         horizontal_space().width(Length::Fixed(20.0)),
         text("Base value").size(20),
         horizontal_space().width(Length::Fixed(10.0)),
-        number_input(base, current, update_base).min(0),
+        number_input(base, current, move |value| Message::UpdateStatBase(
+            stat, value
+        ))
+        .min(0),
     ]
     .into()
 }
 
+/// A dropdown of the keys the game knows about, falling back to a text input for anything else.
+fn view_key_input<'a>(
+    placeholder: &str,
+    strings: &StringTable,
+    value: &[u8],
+    keys: BTreeSet<String>,
+    field: Field,
+    custom_fields: &HashSet<Field>,
+    on_input: fn(String) -> Message,
+) -> Element<'a, Message> {
+    let value = String::from_utf8_lossy(value).to_string();
+    let input: Element<_> = if strings.contains(&value) {
+        row![
+            text_input(placeholder, &value)
+                .width(150)
+                .on_input(on_input),
+            text(strings.get(&value)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .into()
+    } else {
+        text_input(placeholder, &value)
+            .width(150)
+            .on_input(on_input)
+            .into()
+    };
+    if keys.is_empty() {
+        return input;
+    }
+
+    let choice = |key: String| Choice::Key {
+        name: strings.get(&key).to_string(),
+        key,
+    };
+    let selected = if custom_fields.contains(&field) || !keys.contains(&value) {
+        Choice::Other
+    } else {
+        choice(value)
+    };
+    let show_input = selected == Choice::Other;
+    let choices: Vec<Choice> = keys
+        .into_iter()
+        .map(choice)
+        .chain([Choice::Other])
+        .collect();
+    let dropdown = pick_list(choices, Some(selected), move |choice| {
+        Message::ChoiceSelected(field, choice)
+    });
+
+    if show_input {
+        row![dropdown, input].spacing(5).into()
+    } else {
+        dropdown.into()
+    }
+}
+
+/// The genders to pick from, with a soldier's unknown one kept so it can be picked back.
+fn gender_choices(gender: Gender) -> Vec<Gender> {
+    let mut choices = vec![Gender::Male, Gender::Female];
+    if let Gender::Unknown(_) = gender {
+        choices.push(gender);
+    }
+    choices
+}
+
+fn field_label<'a>(field: Field, highlighted: Option<Field>) -> Element<'a, Message> {
+    let label = text(field).size(20);
+    if highlighted == Some(field) {
+        label.style(Color::from_rgb(0.9, 0.5, 0.0)).into()
+    } else {
+        label.into()
+    }
+}
+
+/// Every keyboard shortcut, as shown in the F1 overlay.
+const SHORTCUTS: [(&str, &str); 9] = [
+    ("Ctrl+O", "Open a save"),
+    ("Ctrl+S", "Save"),
+    ("Down / J", "Next soldier in the roster"),
+    ("Up / K", "Previous soldier in the roster"),
+    ("Tab", "Next field"),
+    ("Shift+Tab", "Previous field"),
+    (
+        "+ / -",
+        "Nudge the highlighted stat (click a stat's name to highlight it)",
+    ),
+    ("F1", "Show or hide these shortcuts"),
+    ("Esc", "Hide these shortcuts"),
+];
+
+fn shortcut(event: Event, status: event::Status) -> Option<Message> {
+    let Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event else {
+        return None;
+    };
+    match key.as_ref() {
+        // These work even while typing in a field, which takes every key press for itself
+        Key::Character("o") if modifiers.command() => Some(Message::OpenFile),
+        Key::Character("s") if modifiers.command() => Some(Message::SaveFile),
+        _ if status == event::Status::Captured => None,
+        _ if modifiers.command() || modifiers.alt() => None,
+        Key::Named(Named::ArrowDown) | Key::Character("j") => Some(Message::SelectNextSoldier),
+        Key::Named(Named::ArrowUp) | Key::Character("k") => Some(Message::SelectPreviousSoldier),
+        Key::Named(Named::Tab) if modifiers.shift() => Some(Message::FocusPrevious),
+        Key::Named(Named::Tab) => Some(Message::FocusNext),
+        Key::Character("+" | "=") => Some(Message::NudgeStat(1)),
+        Key::Character("-") => Some(Message::NudgeStat(-1)),
+        Key::Named(Named::F1) => Some(Message::ToggleShortcuts),
+        Key::Named(Named::Escape) => Some(Message::CloseShortcuts),
+        _ => None,
+    }
+}
+
+fn view_shortcuts<'a>() -> Element<'a, Message> {
+    column![
+        text("Keyboard shortcuts").size(30),
+        column(SHORTCUTS.iter().map(|(keys, action)| {
+            row![
+                text(*keys).size(20).width(Length::Fixed(150.0)),
+                text(*action).size(20),
+            ]
+            .into()
+        }))
+        .spacing(10),
+        button("Close").on_press(Message::CloseShortcuts),
+    ]
+    .spacing(20)
+    .padding(40)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+fn window_event(event: Event, _status: event::Status) -> Option<Message> {
+    match event {
+        Event::Window(_, window::Event::Resized { width, height }) => Some(Message::WindowResized(
+            Size::new(width as f32, height as f32),
+        )),
+        Event::Window(id, window::Event::CloseRequested) => Some(Message::CloseRequested(id)),
+        Event::Window(_, window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+        _ => None,
+    }
+}
+
 fn icon<'a, Message>(codepoint: char) -> Element<'a, Message> {
     text(codepoint).font(BOOTSTRAP_FONT).into()
 }
 
-fn load_save(filepath: &PathBuf) -> Result<Save, Box<dyn Error>> {
-    let file = fs::read(filepath)?;
-    let (_, save) = save::parse_save(&file).map_err(|err| err.to_owned())?;
+fn error_dialog(title: &str, error: impl std::fmt::Debug) -> Command<Message> {
+    Command::perform(
+        AsyncMessageDialog::new()
+            .set_level(MessageLevel::Error)
+            .set_title(title)
+            .set_description(format!("{:#?}", error))
+            .show(),
+        |_| Message::DialogClosed,
+    )
+}
+
+async fn pick_file(
+    title: &str,
+    filter_name: &str,
+    extensions: &[&str],
+    directory: Option<PathBuf>,
+) -> Option<PathBuf> {
+    let mut dialog = AsyncFileDialog::new()
+        .set_title(title)
+        .add_filter(filter_name, extensions);
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog
+        .pick_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+async fn pick_save_location(
+    title: &str,
+    filter_name: &str,
+    extensions: &[&str],
+    file_name: String,
+    directory: Option<PathBuf>,
+) -> Option<PathBuf> {
+    let mut dialog = AsyncFileDialog::new()
+        .set_title(title)
+        .add_filter(filter_name, extensions)
+        .set_file_name(file_name);
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+async fn pick_folder(title: &str, directory: Option<PathBuf>) -> Option<PathBuf> {
+    let mut dialog = AsyncFileDialog::new().set_title(title);
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog
+        .pick_folder()
+        .await
+        .map(|folder| folder.path().to_path_buf())
+}
+
+// The loaders below block, but commands run on a thread pool so the window stays responsive.
+// Errors are turned into strings so they can be carried in a message.
+
+async fn find_save_dirs() -> Vec<PathBuf> {
+    savedirs::find_save_dirs(dirs::home_dir().as_deref(), dirs::document_dir().as_deref())
+}
+
+async fn list_saves(dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    browser::list_saves(&dirs)
+}
+
+async fn summarise_save(filepath: PathBuf) -> Result<SaveSummary, String> {
+    browser::summarise_save(&filepath).map_err(|err| err.to_string())
+}
+
+async fn load_save(filepath: PathBuf) -> Result<Save, String> {
+    let file = fs::read(filepath).map_err(|err| err.to_string())?;
+    let (_, save) = save::parse_save(&file).map_err(|err| err.to_owned().to_string())?;
     Result::Ok(save)
 }
+
+async fn write_save(filepath: PathBuf, bytes: Vec<u8>) -> Result<(), String> {
+    fs::write(filepath, bytes).map_err(|err| err.to_string())
+}
+
+async fn load_game_data(install_dir: PathBuf) -> Result<GameData, String> {
+    gamedata::load_game_data(&install_dir).map_err(|err| err.to_string())
+}
+
+/// Where each soldier's fields are in a save file, or nothing for files that aren't saves.
+fn file_spans(bytes: &[u8]) -> SpanMap {
+    save::parse_save_with_spans(bytes)
+        .map(|(_, (_, spans))| spans)
+        .unwrap_or_default()
+}
+
+async fn read_file(filepath: PathBuf) -> Result<Vec<u8>, String> {
+    fs::read(filepath).map_err(|err| err.to_string())
+}
+
+async fn read_names_file(filepath: PathBuf) -> Result<String, String> {
+    fs::read_to_string(filepath).map_err(|err| err.to_string())
+}
+
+/// Saves that have gone missing or have no header are left out.
+async fn read_save_headers(filepaths: Vec<PathBuf>) -> HashMap<PathBuf, SaveHeader> {
+    // The header is a few short strings, so there's no need to read whole saves
+    const HEADER_BYTES: u64 = 4096;
+    filepaths
+        .into_iter()
+        .filter_map(|filepath| {
+            let mut start = vec![];
+            fs::File::open(&filepath)
+                .and_then(|file| file.take(HEADER_BYTES).read_to_end(&mut start))
+                .ok()?;
+            let (_, header) = save::parse_save_header(&start).ok()?;
+            Some((filepath, header))
+        })
+        .collect()
+}
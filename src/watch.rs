@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::subscription::{self, Subscription};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Sends a message whenever something else writes to the file. Games often save by writing a
+/// new file and renaming it over the old one, so the folder is watched rather than the file.
+/// Several messages can come from one save.
+pub fn watch_file<Message>(path: PathBuf, on_change: Message) -> Subscription<Message>
+where
+    Message: Clone + Send + 'static,
+{
+    subscription::channel(
+        (std::any::TypeId::of::<RecommendedWatcher>(), path.clone()),
+        16,
+        move |mut output| async move {
+            let (sender, mut events) = mpsc::channel(16);
+            // Kept alive for as long as the subscription is
+            let _watcher = start_watcher(&path, sender);
+            // Events can name the file either way if it's reached through a symlink
+            let paths = [path.canonicalize().unwrap_or(path.clone()), path];
+            loop {
+                let Some(event) = events.next().await else {
+                    // The watcher couldn't be started, so there's nothing to wait for
+                    iced::futures::future::pending::<()>().await;
+                    continue;
+                };
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                    && event.paths.iter().any(|changed| paths.contains(changed))
+                {
+                    let _ = output.send(on_change.clone()).await;
+                }
+            }
+        },
+    )
+}
+
+fn start_watcher(path: &Path, sender: mpsc::Sender<notify::Event>) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            // Dropped if the editor is behind, since another event will follow soon enough
+            let _ = sender.clone().try_send(event);
+        }
+    })
+    .ok()?;
+    watcher
+        .watch(path.parent()?, RecursiveMode::NonRecursive)
+        .ok()?;
+    Some(watcher)
+}